@@ -0,0 +1,3 @@
+alter table outbox add column priority int not null default 0;
+
+create index idx_outbox_by_priority_and_process_after on outbox (priority desc, process_after) where processed_at is null;
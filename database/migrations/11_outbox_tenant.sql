@@ -0,0 +1,3 @@
+alter table outbox add column tenant varchar(255);
+
+create index idx_outbox_by_tenant_and_process_after on outbox (tenant, process_after) where processed_at is null;
@@ -0,0 +1,3 @@
+alter table outbox add column unordered boolean not null default false;
+
+create index idx_outbox_unordered_by_process_after on outbox (priority desc, process_after) where unordered and processed_at is null;
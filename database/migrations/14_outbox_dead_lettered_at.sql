@@ -0,0 +1 @@
+alter table outbox add column dead_lettered_at timestamptz;
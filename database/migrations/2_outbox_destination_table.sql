@@ -0,0 +1,7 @@
+create table outbox_destination
+(
+    name        varchar(255) not null,
+    destination jsonb        not null,
+    updated_at  timestamptz  not null default now(),
+    primary key (name)
+);
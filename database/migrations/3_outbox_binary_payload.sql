@@ -0,0 +1,3 @@
+alter table outbox add column payload_binary bytea;
+alter table outbox add column content_type varchar(255);
+alter table outbox alter column payload set default '';
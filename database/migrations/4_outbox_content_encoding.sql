@@ -0,0 +1 @@
+alter table outbox add column content_encoding varchar(50);
@@ -0,0 +1,8 @@
+create table outbox_worker
+(
+    worker_id    uuid        not null,
+    heartbeat_at timestamptz not null default now(),
+    primary key (worker_id)
+);
+
+create index idx_outbox_worker_by_heartbeat_at on outbox_worker (heartbeat_at);
@@ -0,0 +1,7 @@
+create table outbox_leader
+(
+    name        varchar(255) not null,
+    holder_id   uuid         not null,
+    lease_until timestamptz  not null,
+    primary key (name)
+);
@@ -0,0 +1,29 @@
+create table if not exists outbox_partition
+(
+    partition_key uuid not null,
+    primary key (partition_key)
+);
+
+-- upserting takes the row lock of every partition key, so a processor draining it waits for the producer
+-- and a partition key deleted in the meantime is inserted again instead of skipped
+create or replace function outbox_partition_register() returns trigger as
+$$
+begin
+    insert into outbox_partition (partition_key)
+    select distinct partition_key from inserted_outbox
+    on conflict (partition_key) do update set partition_key = excluded.partition_key;
+    return null;
+end;
+$$ language plpgsql;
+
+drop trigger if exists trg_outbox_partition_register on outbox;
+
+create trigger trg_outbox_partition_register
+    after insert on outbox
+    referencing new table as inserted_outbox
+    for each statement
+execute function outbox_partition_register();
+
+insert into outbox_partition (partition_key)
+select distinct partition_key from outbox where processed_at is null
+on conflict do nothing;
@@ -0,0 +1,44 @@
+create table outbox
+(
+    idempotent_key   binary(16)   not null,
+    partition_key    binary(16)   not null,
+    destinations     json         not null,
+    headers          json,
+    payload          longtext     not null,
+    payload_binary   longblob,
+    content_type     varchar(255),
+    content_encoding varchar(50),
+    encryption       json,
+    attempts         int          not null default 0,
+    created_at       timestamp(6) not null default current_timestamp(6),
+    process_after    timestamp(6) not null default current_timestamp(6),
+    processed_at     timestamp(6) null,
+    primary key (idempotent_key)
+);
+
+create index idx_outbox_by_partition_key on outbox (partition_key);
+create index idx_outbox_by_process_after on outbox (process_after);
+create index idx_outbox_by_processed_at on outbox (processed_at);
+create index idx_outbox_by_partition_key_and_process_after on outbox (partition_key, process_after);
+create index idx_outbox_by_proc_at_and_proc_after_and_attempt on outbox (processed_at, process_after, attempts);
+
+create table outbox_lock
+(
+    partition_key        binary(16)   not null,
+    lock_id              binary(16)   not null,
+    processed_at         timestamp(6) null,
+    processing_until     timestamp(6) not null default current_timestamp(6),
+    active_partition_key binary(16) generated always as (if(processed_at is null, partition_key, null)) stored
+);
+
+create unique index unq_outbox_lock_by_active_partition_key on outbox_lock (active_partition_key);
+create index idx_outbox_lock_by_lock_id on outbox_lock (lock_id);
+create index idx_outbox_lock_by_processing_until on outbox_lock (processing_until);
+create index idx_outbox_lock_by_processed_at on outbox_lock (processed_at);
+create index idx_outbox_by_partition_key_and_processing_until on outbox_lock (partition_key, processing_until);
+
+create table outbox_cleaner_schedule
+(
+    cron_expression varchar(50)  not null,
+    last_execution  timestamp(6) not null default current_timestamp(6)
+);
@@ -0,0 +1,7 @@
+create table outbox_destination
+(
+    name        varchar(255) not null,
+    destination json         not null,
+    updated_at  timestamp(6) not null default current_timestamp(6),
+    primary key (name)
+);
@@ -0,0 +1,7 @@
+create table outbox_leader
+(
+    name        varchar(255) not null,
+    holder_id   binary(16)   not null,
+    lease_until timestamp(6) not null,
+    primary key (name)
+);
@@ -0,0 +1,26 @@
+create table outbox_job
+(
+    name                    varchar(255) not null,
+    kind                    json         not null,
+    cron_expression         varchar(50)  not null,
+    enabled                 boolean      not null default true,
+    last_run_at             timestamp(6) null,
+    last_result             text,
+    last_duration_in_millis bigint,
+    primary key (name)
+);
+
+insert into outbox_job (name, kind, cron_expression, last_run_at)
+select 'lock_cleanup', '{"kind": "lock_cleanup"}', cron_expression, last_execution
+from outbox_cleaner_schedule
+limit 1;
+
+create table outbox_backlog_snapshot
+(
+    taken_at          timestamp(6) not null default current_timestamp(6),
+    pending           bigint       not null,
+    dead_lettered     bigint       not null,
+    oldest_pending_at timestamp(6) null
+);
+
+create index idx_outbox_backlog_snapshot_by_taken_at on outbox_backlog_snapshot (taken_at);
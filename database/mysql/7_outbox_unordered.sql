@@ -0,0 +1 @@
+alter table outbox add column unordered boolean not null default false;
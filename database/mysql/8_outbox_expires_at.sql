@@ -0,0 +1 @@
+alter table outbox add column expires_at timestamp(6) null;
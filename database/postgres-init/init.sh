@@ -0,0 +1,7 @@
+#!/bin/sh
+set -e
+
+cd /migrations
+for migration in $(ls *.sql | sort -n) optional/*.sql; do
+    psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" --dbname "$POSTGRES_DB" -f "$migration"
+done
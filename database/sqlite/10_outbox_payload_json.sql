@@ -0,0 +1 @@
+alter table outbox add column payload_json text;
@@ -0,0 +1 @@
+alter table outbox_leader add column last_run_at text;
@@ -0,0 +1,25 @@
+create table outbox
+(
+    idempotent_key   blob    not null,
+    partition_key    blob    not null,
+    destinations     text    not null,
+    headers          text,
+    payload          text    not null default '',
+    payload_binary   blob,
+    content_type     varchar(255),
+    content_encoding varchar(50),
+    encryption       text,
+    attempts         integer not null default 0,
+    created_at       text    not null default (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    process_after    text    not null default (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    processed_at     text,
+    processing_until text,
+    primary key (idempotent_key)
+);
+
+create index idx_outbox_by_partition_key on outbox (partition_key);
+create index idx_outbox_by_process_after on outbox (process_after);
+create index idx_outbox_by_processed_at on outbox (processed_at);
+create index idx_outbox_by_partition_key_and_process_after on outbox (partition_key, process_after);
+create index idx_outbox_by_part_key_and_proc_after_and_attempt_and_proc_at on outbox (partition_key, process_after, attempts) where processed_at is null;
+create index idx_outbox_by_part_key_and_processing_until on outbox (partition_key, processing_until) where processed_at is null;
@@ -0,0 +1,7 @@
+create table outbox_destination
+(
+    name        varchar(255) not null,
+    destination text         not null,
+    updated_at  text         not null default (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    primary key (name)
+);
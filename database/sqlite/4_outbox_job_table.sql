@@ -0,0 +1,21 @@
+create table outbox_job
+(
+    name                    varchar(255) not null,
+    kind                    text         not null,
+    cron_expression         varchar(50)  not null,
+    enabled                 boolean      not null default true,
+    last_run_at             text,
+    last_result             text,
+    last_duration_in_millis integer,
+    primary key (name)
+);
+
+create table outbox_backlog_snapshot
+(
+    taken_at          text    not null default (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    pending           integer not null,
+    dead_lettered     integer not null,
+    oldest_pending_at text
+);
+
+create index idx_outbox_backlog_snapshot_by_taken_at on outbox_backlog_snapshot (taken_at);
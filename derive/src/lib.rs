@@ -0,0 +1,101 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Lit, LitStr};
+
+#[proc_macro_derive(OutboxEvent, attributes(outbox))]
+pub fn derive_outbox_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut event_type: Option<LitStr> = None;
+    let mut version: Option<String> = None;
+    let mut destinations: Vec<LitStr> = vec![];
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("outbox")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("event_type") {
+                event_type = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("version") {
+                version = Some(match meta.value()?.parse::<Lit>()? {
+                    Lit::Str(value) => value.value(),
+                    Lit::Int(value) => value.base10_digits().to_string(),
+                    other => return Err(syn::Error::new_spanned(other, "version must be a string or an integer")),
+                });
+                Ok(())
+            } else if meta.path.is_ident("destination") {
+                destinations.push(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported outbox attribute, expected event_type, version or destination"))
+            }
+        })?;
+    }
+
+    let event_type = event_type.ok_or_else(|| syn::Error::new_spanned(&input.ident, "missing #[outbox(event_type = \"...\")] attribute"))?;
+    let version = version.unwrap_or_else(|| "1".to_string());
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "OutboxEvent can only be derived for structs with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "OutboxEvent can only be derived for structs")),
+    };
+
+    let mut partition_key = None;
+    for field in fields {
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("outbox")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("partition_key") {
+                    if partition_key.is_some() {
+                        return Err(meta.error("only one field can be marked as partition_key"));
+                    }
+                    partition_key = field.ident.clone();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported outbox field attribute, expected partition_key"))
+                }
+            })?;
+        }
+    }
+
+    let partition_key = partition_key.ok_or_else(|| syn::Error::new_spanned(&input.ident, "missing #[outbox(partition_key)] field"))?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::outbox_pattern_processor::outbox_event::OutboxEvent for #name #ty_generics #where_clause {
+            fn event_type(&self) -> String {
+                #event_type.to_string()
+            }
+
+            fn event_version(&self) -> String {
+                #version.to_string()
+            }
+
+            fn partition_key(&self) -> ::outbox_pattern_processor::outbox_event::Uuid {
+                ::core::convert::Into::into(::core::clone::Clone::clone(&self.#partition_key))
+            }
+
+            fn destinations(&self) -> Vec<::outbox_pattern_processor::outbox_destination::OutboxDestination> {
+                vec![
+                    #(
+                        ::outbox_pattern_processor::outbox_destination::OutboxDestination::NamedDestination(
+                            ::outbox_pattern_processor::named_destination::NamedDestination {
+                                name: #destinations.to_string(),
+                            }
+                        )
+                    ),*
+                ]
+            }
+        }
+    })
+}
@@ -1,25 +1,61 @@
 use crate::aws::{SnsClient, SqsClient};
+use crate::cloud_events::CloudEventsMode;
+use crate::destination_registry::DestinationRegistry;
 use crate::error::OutboxPatternProcessorError;
+use crate::expired_outbox_action::ExpiredOutboxAction;
+use crate::http_auth_profile::HttpAuthProfile;
 use crate::http_gateway::HttpGateway;
+use crate::lock_strategy::LockStrategy;
+use crate::oauth2_token_cache::OAuth2TokenCache;
+use crate::outbox_storage_backend::OutboxStorageBackend;
+use crate::payload_encryptor::PayloadEncryptor;
+use crate::processing_mode::ProcessingMode;
+use crate::routing_table::RoutingTable;
+use crate::schema_validator::SchemaValidator;
+use crate::secret_resolvers::SecretResolvers;
+use crate::tenant_fairness::TenantFairness;
+use crate::worker_sharding::WorkerSharding;
 use sqlx::{Pool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub postgres_pool: Pool<Postgres>,
+    pub storage: Arc<dyn OutboxStorageBackend>,
+    pub postgres_pool: Option<Pool<Postgres>>,
     pub sqs_client: Option<SqsClient>,
     pub sns_client: Option<SnsClient>,
     pub http_gateway: HttpGateway,
     pub outbox_query_limit: Option<u32>,
     pub delete_after_process_successfully: Option<bool>,
     pub max_in_flight_interval_in_seconds: Option<u64>,
+    pub lease_renewal_interval_in_seconds: Option<u64>,
     pub outbox_failure_limit: Option<u32>,
     pub scheduled_clear_locked_partition: Option<bool>,
     pub delay_for_failure_attempt_in_seconds: Option<u64>,
+    pub http_auth_profiles: HashMap<String, HttpAuthProfile>,
+    pub oauth2_token_cache: OAuth2TokenCache,
+    pub secret_resolvers: SecretResolvers,
+    pub destination_registry: DestinationRegistry,
+    pub routing_table: RoutingTable,
+    pub cloud_events_mode: Option<CloudEventsMode>,
+    pub payload_encryptor: Option<PayloadEncryptor>,
+    pub schema_validator: Option<SchemaValidator>,
+    pub lock_strategy: Option<LockStrategy>,
+    pub worker_sharding: Option<WorkerSharding>,
+    pub priority_fair_share_percentage: Option<u32>,
+    pub tenant_fairness: Option<TenantFairness>,
+    pub processing_mode: Option<ProcessingMode>,
+    pub unordered_query_limit: Option<u32>,
+    pub unordered_http_concurrency: Option<u32>,
+    pub expired_outbox_action: Option<ExpiredOutboxAction>,
 }
 
 impl AppState {
     pub async fn begin_transaction(&self) -> Result<Transaction<'_, Postgres>, OutboxPatternProcessorError> {
         self.postgres_pool
+            .as_ref()
+            .ok_or_else(|| OutboxPatternProcessorError::new("Storage is not Postgres", "Failed to create transaction"))?
             .begin()
             .await
             .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to create transaction"))
@@ -53,3 +53,78 @@ impl SqsClient {
         SqsClient { client }
     }
 }
+
+#[derive(Clone)]
+pub struct SecretsManagerClient {
+    pub client: aws_sdk_secretsmanager::Client,
+}
+
+#[derive(Clone)]
+pub struct SsmClient {
+    pub client: aws_sdk_ssm::Client,
+}
+
+#[derive(Clone)]
+pub struct KmsClient {
+    pub client: aws_sdk_kms::Client,
+}
+
+impl SecretsManagerClient {
+    pub async fn new(aws_config: &aws_config::SdkConfig) -> SecretsManagerClient {
+        let endpoint = env::var("LOCAL_ENDPOINT").ok();
+        let region = env::var("LOCAL_REGION").map(|region| Region::new(Cow::Owned(region))).ok();
+
+        let client = match endpoint {
+            None => aws_sdk_secretsmanager::Client::new(aws_config),
+            Some(url) => aws_sdk_secretsmanager::Client::from_conf(
+                aws_sdk_secretsmanager::config::Builder::from(aws_config)
+                    .endpoint_url(url)
+                    .region(region)
+                    .credentials_provider(DefaultCredentialsChain::builder().build().await)
+                    .build(),
+            ),
+        };
+
+        SecretsManagerClient { client }
+    }
+}
+
+impl SsmClient {
+    pub async fn new(aws_config: &aws_config::SdkConfig) -> SsmClient {
+        let endpoint = env::var("LOCAL_ENDPOINT").ok();
+        let region = env::var("LOCAL_REGION").map(|region| Region::new(Cow::Owned(region))).ok();
+
+        let client = match endpoint {
+            None => aws_sdk_ssm::Client::new(aws_config),
+            Some(url) => aws_sdk_ssm::Client::from_conf(
+                aws_sdk_ssm::config::Builder::from(aws_config)
+                    .endpoint_url(url)
+                    .region(region)
+                    .credentials_provider(DefaultCredentialsChain::builder().build().await)
+                    .build(),
+            ),
+        };
+
+        SsmClient { client }
+    }
+}
+
+impl KmsClient {
+    pub async fn new(aws_config: &aws_config::SdkConfig) -> KmsClient {
+        let endpoint = env::var("LOCAL_ENDPOINT").ok();
+        let region = env::var("LOCAL_REGION").map(|region| Region::new(Cow::Owned(region))).ok();
+
+        let client = match endpoint {
+            None => aws_sdk_kms::Client::new(aws_config),
+            Some(url) => aws_sdk_kms::Client::from_conf(
+                aws_sdk_kms::config::Builder::from(aws_config)
+                    .endpoint_url(url)
+                    .region(region)
+                    .credentials_provider(DefaultCredentialsChain::builder().build().await)
+                    .build(),
+            ),
+        };
+
+        KmsClient { client }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::aws::KmsClient;
+use crate::data_key_provider::{DataKey, DataKeyProvider};
+use crate::error::OutboxPatternProcessorError;
+use async_trait::async_trait;
+use aws_sdk_kms::error::ProvideErrorMetadata;
+use aws_sdk_kms::primitives::Blob;
+use aws_sdk_kms::types::DataKeySpec;
+
+pub struct KmsDataKeyProvider {
+    pub client: KmsClient,
+    pub key_id: String,
+}
+
+impl KmsDataKeyProvider {
+    pub fn new(
+        client: KmsClient,
+        key_id: &str,
+    ) -> Self {
+        Self {
+            client,
+            key_id: key_id.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl DataKeyProvider for KmsDataKeyProvider {
+    async fn generate_data_key(&self) -> Result<DataKey, OutboxPatternProcessorError> {
+        let output = self
+            .client
+            .client
+            .generate_data_key()
+            .key_id(&self.key_id)
+            .key_spec(DataKeySpec::Aes256)
+            .send()
+            .await
+            .map_err(|error| {
+                OutboxPatternProcessorError::new(
+                    error.message().unwrap_or(&error.to_string()),
+                    &format!("Failed to generate data key with kms key {}", self.key_id),
+                )
+            })?;
+
+        match (output.plaintext, output.ciphertext_blob) {
+            (Some(plaintext), Some(encrypted)) => Ok(DataKey {
+                key_id: output.key_id.unwrap_or(self.key_id.clone()),
+                plaintext: plaintext.into_inner(),
+                encrypted: encrypted.into_inner(),
+            }),
+            _ => Err(OutboxPatternProcessorError::new(
+                "Missing data key on kms response",
+                &format!("Failed to generate data key with kms key {}", self.key_id),
+            )),
+        }
+    }
+
+    async fn decrypt_data_key(
+        &self,
+        key_id: &str,
+        encrypted_key: &[u8],
+    ) -> Result<Vec<u8>, OutboxPatternProcessorError> {
+        let output = self
+            .client
+            .client
+            .decrypt()
+            .key_id(key_id)
+            .ciphertext_blob(Blob::new(encrypted_key))
+            .send()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(error.message().unwrap_or(&error.to_string()), &format!("Failed to decrypt data key with kms key {key_id}")))?;
+
+        output
+            .plaintext
+            .map(|plaintext| plaintext.into_inner())
+            .ok_or_else(|| OutboxPatternProcessorError::new("Missing plaintext on kms response", &format!("Failed to decrypt data key with kms key {key_id}")))
+    }
+}
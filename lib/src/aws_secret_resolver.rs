@@ -0,0 +1,61 @@
+use crate::aws::{SecretsManagerClient, SsmClient};
+use crate::error::OutboxPatternProcessorError;
+use crate::secret_resolver::SecretResolver;
+use async_trait::async_trait;
+use aws_sdk_secretsmanager::error::ProvideErrorMetadata;
+
+pub struct SecretsManagerSecretResolver {
+    pub client: SecretsManagerClient,
+}
+
+impl SecretsManagerSecretResolver {
+    pub fn new(client: SecretsManagerClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl SecretResolver for SecretsManagerSecretResolver {
+    async fn resolve(
+        &self,
+        key: &str,
+    ) -> Result<Option<String>, OutboxPatternProcessorError> {
+        let output = self
+            .client
+            .client
+            .get_secret_value()
+            .secret_id(key)
+            .send()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(error.message().unwrap_or(&error.to_string()), &format!("Failed to get secret {key} from secrets manager")))?;
+
+        Ok(output.secret_string().map(|value| value.to_string()))
+    }
+}
+
+pub struct SsmParameterSecretResolver {
+    pub client: SsmClient,
+}
+
+impl SsmParameterSecretResolver {
+    pub fn new(client: SsmClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl SecretResolver for SsmParameterSecretResolver {
+    async fn resolve(
+        &self,
+        key: &str,
+    ) -> Result<Option<String>, OutboxPatternProcessorError> {
+        let output = self.client.client.get_parameter().name(key).with_decryption(true).send().await.map_err(|error| {
+            OutboxPatternProcessorError::new(
+                error.message().unwrap_or(&error.to_string()),
+                &format!("Failed to get parameter {key} from ssm parameter store"),
+            )
+        })?;
+
+        Ok(output.parameter().and_then(|parameter| parameter.value()).map(|value| value.to_string()))
+    }
+}
@@ -0,0 +1,151 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::types::Json;
+use std::collections::HashMap;
+
+const SPEC_VERSION: &str = "1.0";
+const STRUCTURED_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CloudEventsMode {
+    Structured,
+    Binary,
+    Disabled,
+}
+
+impl CloudEventsMode {
+    pub fn wrap(
+        &self,
+        outbox: &Outbox,
+    ) -> Result<Outbox, OutboxPatternProcessorError> {
+        match self {
+            CloudEventsMode::Structured => Self::wrap_structured(outbox),
+            CloudEventsMode::Binary => Self::wrap_binary(outbox),
+            CloudEventsMode::Disabled => Ok(outbox.clone()),
+        }
+    }
+
+    pub fn is_envelope_header(
+        outbox: &Outbox,
+        key: &str,
+    ) -> bool {
+        let wrapped = outbox.content_type.as_deref() == Some(STRUCTURED_CONTENT_TYPE)
+            || outbox
+                .headers
+                .as_ref()
+                .map(|headers| headers.0.keys().any(|header| header.eq_ignore_ascii_case("ce-specversion")))
+                .unwrap_or(false);
+
+        wrapped && (key.eq_ignore_ascii_case("content-type") || key.to_ascii_lowercase().starts_with("ce-"))
+    }
+
+    fn wrap_structured(outbox: &Outbox) -> Result<Outbox, OutboxPatternProcessorError> {
+        let attributes = Self::attributes(outbox)?;
+        let mut headers = Self::headers_without_attributes(outbox);
+
+        let mut event = Map::new();
+        for (key, value) in attributes {
+            event.insert(key, Value::String(value));
+        }
+
+        match serde_json::from_str::<Value>(&outbox.payload) {
+            _ if outbox.is_binary() => {
+                event.insert("data_base64".to_string(), Value::String(outbox.encoded_payload()));
+            },
+            Ok(data)
+                if event
+                    .get("datacontenttype")
+                    .and_then(Value::as_str)
+                    .map(|content_type| content_type.contains("json"))
+                    .unwrap_or(false) =>
+            {
+                event.insert("data".to_string(), data);
+            },
+            _ => {
+                event.insert("data".to_string(), Value::String(outbox.payload.clone()));
+            },
+        }
+
+        headers.insert("content-type".to_string(), STRUCTURED_CONTENT_TYPE.to_string());
+
+        Ok(Outbox {
+            headers: Some(Json(headers)),
+            payload: Value::Object(event).to_string(),
+            payload_binary: None,
+            content_type: Some(STRUCTURED_CONTENT_TYPE.to_string()),
+            content_encoding: None,
+            encryption: None,
+            ..outbox.clone()
+        })
+    }
+
+    fn wrap_binary(outbox: &Outbox) -> Result<Outbox, OutboxPatternProcessorError> {
+        let attributes = Self::attributes(outbox)?;
+        let mut headers = Self::headers_without_attributes(outbox);
+
+        for (key, value) in attributes {
+            if key == "datacontenttype" {
+                headers.insert("content-type".to_string(), value);
+            } else {
+                headers.insert(format!("ce-{key}"), value);
+            }
+        }
+
+        Ok(Outbox {
+            headers: Some(Json(headers)),
+            ..outbox.clone()
+        })
+    }
+
+    fn attributes(outbox: &Outbox) -> Result<Vec<(String, String)>, OutboxPatternProcessorError> {
+        let header = |name: &str| {
+            outbox
+                .headers
+                .as_ref()
+                .and_then(|headers| headers.0.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.clone()))
+        };
+
+        let event_type = header("ce-type").ok_or_else(|| OutboxPatternProcessorError::new("Missing ce-type header", "Failed to create cloud event"))?;
+        let source = header("ce-source").ok_or_else(|| OutboxPatternProcessorError::new("Missing ce-source header", "Failed to create cloud event"))?;
+
+        let data_content_type = header("content-type").or(outbox.content_type.clone()).unwrap_or_else(|| {
+            if serde_json::from_str::<Value>(&outbox.payload).is_ok() {
+                "application/json".to_string()
+            } else {
+                "text/plain".to_string()
+            }
+        });
+
+        let mut attributes = vec![
+            ("specversion".to_string(), SPEC_VERSION.to_string()),
+            ("id".to_string(), outbox.idempotent_key.to_string()),
+            ("source".to_string(), source),
+            ("type".to_string(), event_type),
+            ("time".to_string(), outbox.created_at.to_rfc3339()),
+            ("datacontenttype".to_string(), data_content_type),
+        ];
+
+        if let Some(subject) = header("ce-subject") {
+            attributes.push(("subject".to_string(), subject));
+        }
+
+        Ok(attributes)
+    }
+
+    fn headers_without_attributes(outbox: &Outbox) -> HashMap<String, String> {
+        outbox
+            .headers
+            .as_ref()
+            .map(|headers| headers.0.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| {
+                let key = key.to_lowercase();
+                !key.starts_with("ce-") && key != "content-type"
+            })
+            .collect()
+    }
+}
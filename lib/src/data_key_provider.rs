@@ -0,0 +1,104 @@
+use crate::error::OutboxPatternProcessorError;
+use aes_gcm::aead::{Aead, OsRng};
+use aes_gcm::{AeadCore, Aes256Gcm, Key, KeyInit, Nonce};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Clone)]
+pub struct DataKey {
+    pub key_id: String,
+    pub plaintext: Vec<u8>,
+    pub encrypted: Vec<u8>,
+}
+
+#[async_trait]
+pub trait DataKeyProvider: Send + Sync {
+    async fn generate_data_key(&self) -> Result<DataKey, OutboxPatternProcessorError>;
+
+    async fn decrypt_data_key(
+        &self,
+        key_id: &str,
+        encrypted_key: &[u8],
+    ) -> Result<Vec<u8>, OutboxPatternProcessorError>;
+}
+
+pub struct LocalDataKeyProvider {
+    pub current_key_id: String,
+    pub keys: HashMap<String, [u8; 32]>,
+}
+
+impl LocalDataKeyProvider {
+    pub fn new(
+        current_key_id: &str,
+        key: [u8; 32],
+    ) -> Self {
+        Self {
+            current_key_id: current_key_id.to_string(),
+            keys: HashMap::from([(current_key_id.to_string(), key)]),
+        }
+    }
+
+    pub fn with_key(
+        self,
+        key_id: &str,
+        key: [u8; 32],
+    ) -> Self {
+        let mut keys = self.keys;
+        keys.insert(key_id.to_string(), key);
+
+        Self {
+            current_key_id: self.current_key_id,
+            keys,
+        }
+    }
+
+    fn master_key(
+        &self,
+        key_id: &str,
+    ) -> Result<Aes256Gcm, OutboxPatternProcessorError> {
+        self.keys
+            .get(key_id)
+            .map(|key| Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)))
+            .ok_or_else(|| OutboxPatternProcessorError::new(&format!("Unknown key id {key_id}"), "Failed to find local master key"))
+    }
+}
+
+#[async_trait]
+impl DataKeyProvider for LocalDataKeyProvider {
+    async fn generate_data_key(&self) -> Result<DataKey, OutboxPatternProcessorError> {
+        let plaintext = Aes256Gcm::generate_key(OsRng).to_vec();
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let wrapped_key = self
+            .master_key(&self.current_key_id)?
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to encrypt data key"))?;
+
+        let mut encrypted = nonce.to_vec();
+        encrypted.extend(wrapped_key);
+
+        Ok(DataKey {
+            key_id: self.current_key_id.clone(),
+            plaintext,
+            encrypted,
+        })
+    }
+
+    async fn decrypt_data_key(
+        &self,
+        key_id: &str,
+        encrypted_key: &[u8],
+    ) -> Result<Vec<u8>, OutboxPatternProcessorError> {
+        if encrypted_key.len() <= NONCE_LENGTH {
+            return Err(OutboxPatternProcessorError::new("Encrypted data key is too short", "Failed to decrypt data key"));
+        }
+
+        let (nonce, wrapped_key) = encrypted_key.split_at(NONCE_LENGTH);
+
+        self.master_key(key_id)?
+            .decrypt(Nonce::from_slice(nonce), wrapped_key)
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to decrypt data key with key id {key_id}")))
+    }
+}
@@ -0,0 +1,38 @@
+use crate::outbox_destination::OutboxDestination;
+use std::collections::HashMap;
+
+#[derive(Clone, Default)]
+pub struct DestinationRegistry {
+    pub destinations: HashMap<String, OutboxDestination>,
+    pub load_from_database: bool,
+}
+
+impl DestinationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_destination(
+        self,
+        name: &str,
+        destination: OutboxDestination,
+    ) -> Self {
+        let mut destinations = self.destinations;
+        destinations.insert(name.to_string(), destination);
+
+        Self {
+            destinations,
+            load_from_database: self.load_from_database,
+        }
+    }
+
+    pub fn with_load_from_database(
+        self,
+        load_from_database: bool,
+    ) -> Self {
+        Self {
+            destinations: self.destinations,
+            load_from_database,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::outbox_columns::{OutboxColumns, OUTBOX_INSERT_MAX_ROWS};
+use diesel::pg::PgConnection;
+use diesel::sql_types::{Bool, Bytea, Integer, Jsonb, Nullable, Text, Timestamptz, Uuid};
+use diesel::{Connection, RunQueryDsl};
+
+pub struct DieselOutboxRepository;
+
+impl DieselOutboxRepository {
+    pub fn insert(
+        db_conn: &mut PgConnection,
+        outbox: Outbox,
+    ) -> Result<Outbox, OutboxPatternProcessorError> {
+        let outboxes = Self::insert_all(db_conn, vec![outbox])?;
+        Ok(outboxes[0].clone())
+    }
+
+    pub fn insert_all(
+        db_conn: &mut PgConnection,
+        outboxes: Vec<Outbox>,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        if outboxes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        if outboxes.len() <= OUTBOX_INSERT_MAX_ROWS {
+            Self::insert_chunk(db_conn, &outboxes)?;
+        } else {
+            db_conn.transaction(|conn| outboxes.chunks(OUTBOX_INSERT_MAX_ROWS).try_for_each(|chunk| Self::insert_chunk(conn, chunk)))?;
+        }
+
+        Ok(outboxes)
+    }
+
+    fn insert_chunk(
+        db_conn: &mut PgConnection,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut query = diesel::sql_query(OutboxColumns::insert_statement(outboxes.len())).into_boxed();
+
+        for outbox in outboxes {
+            let row = OutboxColumns::from_outbox(outbox)?;
+
+            query = query
+                .bind::<Uuid, _>(row.idempotent_key)
+                .bind::<Uuid, _>(row.partition_key)
+                .bind::<Jsonb, _>(row.destinations)
+                .bind::<Nullable<Jsonb>, _>(row.headers)
+                .bind::<Text, _>(row.payload)
+                .bind::<Nullable<Bytea>, _>(row.payload_binary)
+                .bind::<Nullable<Text>, _>(row.content_type)
+                .bind::<Nullable<Text>, _>(row.content_encoding)
+                .bind::<Nullable<Jsonb>, _>(row.encryption)
+                .bind::<Timestamptz, _>(row.created_at)
+                .bind::<Timestamptz, _>(row.process_after)
+                .bind::<Integer, _>(row.priority)
+                .bind::<Nullable<Text>, _>(row.tenant)
+                .bind::<Bool, _>(row.unordered)
+                .bind::<Nullable<Timestamptz>, _>(row.expires_at)
+                .bind::<Nullable<Jsonb>, _>(row.payload_json);
+        }
+
+        query.execute(db_conn).map_err(|error| {
+            OutboxPatternProcessorError::new(
+                &error.to_string(),
+                &format!(
+                    "Failed to insert outboxes to partition_keys={}",
+                    outboxes.iter().map(|it| it.partition_key.to_string()).collect::<Vec<_>>().join(", ")
+                ),
+            )
+        })?;
+
+        Ok(())
+    }
+}
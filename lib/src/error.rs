@@ -30,3 +30,10 @@ impl fmt::Display for OutboxPatternProcessorError {
         write!(f, "{}", self.cause)
     }
 }
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for OutboxPatternProcessorError {
+    fn from(error: diesel::result::Error) -> Self {
+        Self::new(&error.to_string(), "Failed to run diesel transaction")
+    }
+}
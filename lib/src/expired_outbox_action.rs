@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpiredOutboxAction {
+    #[default]
+    DeadLetter,
+    Discard,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HttpAuthProfile {
+    #[serde(rename = "oauth2_client_credentials")]
+    OAuth2ClientCredentials {
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        scope: Option<String>,
+    },
+    Basic {
+        username: String,
+        password: String,
+    },
+    MutualTls {
+        cert_path: String,
+        key_path: String,
+        ca_cert_path: Option<String>,
+    },
+}
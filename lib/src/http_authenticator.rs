@@ -0,0 +1,111 @@
+use crate::app_state::AppState;
+use crate::error::OutboxPatternProcessorError;
+use crate::http_auth_profile::HttpAuthProfile;
+use crate::oauth2_token_cache::OAuth2Token;
+use reqwest_middleware::RequestBuilder;
+use serde::Deserialize;
+use sqlx::types::chrono::Utc;
+use std::time::Duration;
+use tracing::instrument;
+
+const OAUTH2_EXPIRATION_SKEW_IN_SECONDS: u64 = 30;
+
+#[derive(Deserialize)]
+struct OAuth2TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+pub struct HttpAuthenticator;
+
+impl HttpAuthenticator {
+    #[instrument(skip_all)]
+    pub async fn authenticate(
+        app_state: &AppState,
+        profile_name: &str,
+        request: RequestBuilder,
+    ) -> Result<RequestBuilder, OutboxPatternProcessorError> {
+        let profile = app_state
+            .http_auth_profiles
+            .get(profile_name)
+            .ok_or_else(|| OutboxPatternProcessorError::new(&format!("Unknown http auth profile {profile_name}"), "Failed to authenticate http request"))?;
+
+        match profile {
+            HttpAuthProfile::OAuth2ClientCredentials {
+                token_url,
+                client_id,
+                client_secret,
+                scope,
+            } => {
+                let client_id = app_state.secret_resolvers.resolve_placeholders(client_id).await;
+                let client_secret = app_state.secret_resolvers.resolve_placeholders(client_secret).await;
+                let token = Self::oauth2_token(app_state, profile_name, token_url, &client_id, &client_secret, scope).await?;
+                Ok(request.bearer_auth(token.access_token))
+            },
+            HttpAuthProfile::Basic { username, password } => {
+                let username = app_state.secret_resolvers.resolve_placeholders(username).await;
+                let password = app_state.secret_resolvers.resolve_placeholders(password).await;
+                Ok(request.basic_auth(username, Some(password)))
+            },
+            HttpAuthProfile::MutualTls { .. } => Ok(request),
+        }
+    }
+
+    pub async fn invalidate(
+        app_state: &AppState,
+        profile_name: &str,
+    ) {
+        app_state.oauth2_token_cache.invalidate(profile_name).await;
+    }
+
+    async fn oauth2_token(
+        app_state: &AppState,
+        profile_name: &str,
+        token_url: &str,
+        client_id: &str,
+        client_secret: &str,
+        scope: &Option<String>,
+    ) -> Result<OAuth2Token, OutboxPatternProcessorError> {
+        if let Some(token) = app_state.oauth2_token_cache.get(profile_name).await {
+            return Ok(token);
+        }
+
+        let mut form = vec![("grant_type", "client_credentials")];
+        if let Some(scope) = scope {
+            form.push(("scope", scope));
+        }
+
+        let response = app_state
+            .http_gateway
+            .client
+            .post(token_url)
+            .basic_auth(client_id, Some(client_secret))
+            .form(&form)
+            .send()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to request oauth2 token for profile {profile_name}")))?;
+
+        if !response.status().is_success() {
+            return Err(OutboxPatternProcessorError::new(
+                &response.text().await.unwrap_or("unknown".to_string()),
+                &format!("Failed to request oauth2 token for profile {profile_name}"),
+            ));
+        }
+
+        let token_response = response
+            .json::<OAuth2TokenResponse>()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to parse oauth2 token for profile {profile_name}")))?;
+
+        let token = OAuth2Token {
+            access_token: token_response.access_token,
+            expires_at: token_response
+                .expires_in
+                .map(|expires_in| Utc::now() + Duration::from_secs(expires_in.saturating_sub(OAUTH2_EXPIRATION_SKEW_IN_SECONDS))),
+        };
+
+        app_state.oauth2_token_cache.put(profile_name, token.clone()).await;
+
+        Ok(token)
+    }
+}
@@ -1,3 +1,6 @@
+use crate::cloud_events::CloudEventsMode;
+use crate::payload_compression::PayloadCompression;
+use crate::payload_transform::PayloadTransform;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::collections::HashMap;
@@ -7,4 +10,8 @@ pub struct HttpDestination {
     pub url: String,
     pub headers: Option<HashMap<String, String>>,
     pub method: Option<String>,
+    pub auth: Option<String>,
+    pub transforms: Option<Vec<PayloadTransform>>,
+    pub cloud_events: Option<CloudEventsMode>,
+    pub compression: Option<PayloadCompression>,
 }
@@ -1,12 +1,17 @@
+use std::collections::HashMap;
+use std::fs;
 use std::time::Duration;
 
 use crate::error::OutboxPatternProcessorError;
-use reqwest::Client;
+use crate::http_auth_profile::HttpAuthProfile;
+use reqwest::{Certificate, Client, Identity};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 
 #[derive(Clone)]
 pub struct HttpGateway {
     pub client: ClientWithMiddleware,
+    pub mutual_tls_clients: HashMap<String, ClientWithMiddleware>,
+    request_timeout: u64,
 }
 
 impl HttpGateway {
@@ -19,6 +24,78 @@ impl HttpGateway {
         )
         .build();
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            mutual_tls_clients: HashMap::new(),
+            request_timeout,
+        })
+    }
+
+    pub fn with_http_auth_profiles(
+        self,
+        http_auth_profiles: &HashMap<String, HttpAuthProfile>,
+    ) -> Result<Self, OutboxPatternProcessorError> {
+        let mut mutual_tls_clients = self.mutual_tls_clients;
+        for (profile_name, profile) in http_auth_profiles {
+            if let HttpAuthProfile::MutualTls {
+                cert_path,
+                key_path,
+                ca_cert_path,
+            } = profile
+            {
+                mutual_tls_clients.insert(
+                    profile_name.clone(),
+                    Self::mutual_tls_client(self.request_timeout, profile_name, cert_path, key_path, ca_cert_path.as_deref())?,
+                );
+            }
+        }
+
+        Ok(Self {
+            client: self.client,
+            mutual_tls_clients,
+            request_timeout: self.request_timeout,
+        })
+    }
+
+    pub fn client(
+        &self,
+        auth: Option<&String>,
+    ) -> &ClientWithMiddleware {
+        auth.and_then(|profile_name| self.mutual_tls_clients.get(profile_name)).unwrap_or(&self.client)
+    }
+
+    fn mutual_tls_client(
+        request_timeout: u64,
+        profile_name: &str,
+        cert_path: &str,
+        key_path: &str,
+        ca_cert_path: Option<&str>,
+    ) -> Result<ClientWithMiddleware, OutboxPatternProcessorError> {
+        let mut pem =
+            fs::read(cert_path).map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to read client certificate for profile {profile_name}")))?;
+        pem.push(b'\n');
+        pem.extend(fs::read(key_path).map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to read client key for profile {profile_name}")))?);
+
+        let identity = Identity::from_pem(&pem)
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to load client identity for profile {profile_name}")))?;
+
+        let mut client_builder = Client::builder().timeout(Duration::from_secs(request_timeout)).identity(identity);
+
+        if let Some(ca_cert_path) = ca_cert_path {
+            let ca_cert = fs::read(ca_cert_path)
+                .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to read ca certificate for profile {profile_name}")))
+                .and_then(|ca_pem| {
+                    Certificate::from_pem(&ca_pem)
+                        .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to load ca certificate for profile {profile_name}")))
+                })?;
+
+            client_builder = client_builder.add_root_certificate(ca_cert);
+        }
+
+        let client = client_builder
+            .build()
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to create mutual tls client for profile {profile_name}")))?;
+
+        Ok(ClientBuilder::new(client).build())
     }
 }
@@ -1,10 +1,14 @@
 use crate::app_state::AppState;
-use crate::environment::Environment;
+use crate::cloud_events::CloudEventsMode;
 use crate::error::OutboxPatternProcessorError;
+use crate::http_authenticator::HttpAuthenticator;
+use crate::http_destination::HttpDestination;
 use crate::notification::NotificationResult;
-use crate::outbox_destination::OutboxDestination;
+use crate::outbox::Outbox;
 use crate::outbox_group::GroupedOutboxed;
-use regex::Regex;
+use reqwest::StatusCode;
+use std::sync::Arc;
+use tokio::task::JoinSet;
 use tracing::instrument;
 use tracing::log::error;
 
@@ -18,65 +22,143 @@ impl HttpNotificationService {
     ) -> Result<NotificationResult, OutboxPatternProcessorError> {
         let mut notification_result = NotificationResult::default();
 
-        for outbox in outboxes.http.clone() {
-            for destination in outbox.destinations.0.clone() {
-                if let OutboxDestination::HttpDestination(http) = destination {
-                    let method = http.method.unwrap_or("POST".to_string()).to_uppercase();
-                    let mut request = match method.as_str() {
-                        "PUT" => app_state.http_gateway.client.put(&http.url),
-                        "PATCH" => app_state.http_gateway.client.patch(&http.url),
-                        _ => app_state.http_gateway.client.post(&http.url),
-                    };
-
-                    if let Some(headers) = http.headers {
-                        for (key, value) in headers {
-                            if let Ok(regex) = Regex::new("^\\{\\{[A-Z_]+}}$") {
-                                if regex.is_match(&value) {
-                                    let normalized_value_env_name = value.replace(['{', '}'], "");
-                                    let env_value = Environment::string(&normalized_value_env_name, &value);
-                                    request = request.header(key, env_value);
-                                } else {
-                                    request = request.header(key, value);
-                                }
-                            } else {
-                                request = request.header(key, value);
-                            }
-                        }
-                    }
+        let (unordered, ordered): (Vec<_>, Vec<_>) = outboxes.http.clone().into_iter().partition(|(_, outbox)| outbox.unordered);
 
-                    if let Some(headers) = outbox.headers.clone() {
-                        for (key, value) in headers.0 {
-                            request = request.header(key, value);
-                        }
-                    }
+        for (http, outbox) in ordered {
+            if Self::notify(app_state, http, &outbox).await {
+                notification_result.sent.push(outbox);
+            } else {
+                notification_result.failed.push(outbox);
+            }
+        }
 
-                    request = request.header("x-idempotent-key", outbox.idempotent_key.to_string());
+        let shared_app_state = Arc::new(app_state.clone());
+        for chunk in unordered.chunks(app_state.unordered_http_concurrency.unwrap_or(10).max(1) as usize) {
+            let mut pending = chunk.iter().map(|(_, outbox)| outbox.clone()).collect::<Vec<Outbox>>();
 
-                    let result = request.body(outbox.payload.clone()).send().await;
+            let mut notifications = JoinSet::new();
+            for (http, outbox) in chunk.iter().cloned() {
+                let app_state = shared_app_state.clone();
+                notifications.spawn(async move {
+                    let notified = Self::notify(&app_state, http, &outbox).await;
+                    (outbox, notified)
+                });
+            }
 
-                    if let Ok(response) = result {
-                        if response.status().is_success() {
-                            notification_result.sent.push(outbox.clone());
+            while let Some(result) = notifications.join_next().await {
+                match result {
+                    Ok((outbox, notified)) => {
+                        pending.retain(|it| it.idempotent_key != outbox.idempotent_key);
+                        if notified {
+                            notification_result.sent.push(outbox);
                         } else {
-                            notification_result.failed.push(outbox.clone());
-                            error!(
-                                "Failed to send http notification for idempotent_key {} with status {} and body {}",
-                                outbox.idempotent_key,
-                                response.status(),
-                                response.text().await.unwrap_or("unknown".to_string())
-                            );
+                            notification_result.failed.push(outbox);
                         }
-                    } else {
-                        notification_result.failed.push(outbox.clone());
-                        error!(
-                            "Failed to send http notification cause {}",
-                            result.err().map(|error| error.to_string()).unwrap_or("unknown".to_string())
-                        );
-                    }
+                    },
+                    Err(error) => error!("Failed to send http notification cause {}", error),
                 }
             }
+
+            notification_result.failed.extend(pending);
         }
 
         Ok(notification_result)
     }
+
+    async fn notify(
+        app_state: &AppState,
+        http: HttpDestination,
+        outbox: &Outbox,
+    ) -> bool {
+        let client = app_state.http_gateway.client(http.auth.as_ref());
+
+        let method = http.method.unwrap_or("POST".to_string()).to_uppercase();
+        let mut request = match method.as_str() {
+            "PUT" => client.put(&http.url),
+            "PATCH" => client.patch(&http.url),
+            _ => client.post(&http.url),
+        };
+
+        if let Some(headers) = http.headers.clone() {
+            for (key, value) in headers {
+                if CloudEventsMode::is_envelope_header(outbox, &key) {
+                    continue;
+                }
+
+                request = request.header(key, value);
+            }
+        }
+
+        if let Some(headers) = outbox.headers.clone() {
+            for (key, value) in headers.0 {
+                request = request.header(key, value);
+            }
+        }
+
+        if let Some(content_type) = &outbox.content_type {
+            let has_content_type_header = outbox
+                .headers
+                .as_ref()
+                .map(|outbox_headers| outbox_headers.0.keys().any(|key| key.eq_ignore_ascii_case("content-type")))
+                .unwrap_or(false)
+                || http
+                    .headers
+                    .as_ref()
+                    .map(|http_headers| http_headers.keys().any(|key| key.eq_ignore_ascii_case("content-type")))
+                    .unwrap_or(false);
+
+            if !has_content_type_header {
+                request = request.header("content-type", content_type);
+            }
+        }
+
+        if let Some(content_encoding) = &outbox.content_encoding {
+            request = request.header("content-encoding", content_encoding);
+        }
+
+        request = request.header("x-idempotent-key", outbox.idempotent_key.to_string());
+
+        if let Some(profile_name) = &http.auth {
+            match HttpAuthenticator::authenticate(app_state, profile_name, request).await {
+                Ok(authenticated_request) => request = authenticated_request,
+                Err(error) => {
+                    error!(
+                        "Failed to authenticate http notification for idempotent_key {} - {} - Cause: {}",
+                        outbox.idempotent_key,
+                        error.message.unwrap_or("unknown".to_string()),
+                        error.cause
+                    );
+                    return false;
+                },
+            }
+        }
+
+        let result = request.body(outbox.body_bytes()).send().await;
+
+        if let Ok(response) = result {
+            if response.status().is_success() {
+                true
+            } else {
+                if response.status() == StatusCode::UNAUTHORIZED {
+                    if let Some(profile_name) = &http.auth {
+                        HttpAuthenticator::invalidate(app_state, profile_name).await;
+                    }
+                }
+
+                error!(
+                    "Failed to send http notification for idempotent_key {} with status {} and body {}",
+                    outbox.idempotent_key,
+                    response.status(),
+                    response.text().await.unwrap_or("unknown".to_string())
+                );
+                false
+            }
+        } else {
+            error!(
+                "Failed to send http notification cause {}",
+                result.err().map(|error| error.to_string()).unwrap_or("unknown".to_string())
+            );
+            false
+        }
+    }
 }
@@ -0,0 +1,57 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox_storage::OutboxStorage;
+use crate::outbox_storage_backend::OutboxStorageBackend;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct LeaderElection {
+    storage: Arc<dyn OutboxStorageBackend>,
+    pub holder_id: Uuid,
+    pub lease_duration_in_seconds: u64,
+}
+
+impl LeaderElection {
+    pub fn new(
+        storage: impl Into<OutboxStorage>,
+        holder_id: Uuid,
+    ) -> Self {
+        Self::from_backend(storage.into().backend(), holder_id)
+    }
+
+    pub(crate) fn from_backend(
+        storage: Arc<dyn OutboxStorageBackend>,
+        holder_id: Uuid,
+    ) -> Self {
+        Self {
+            storage,
+            holder_id,
+            lease_duration_in_seconds: 30,
+        }
+    }
+
+    pub fn with_lease_duration_in_seconds(
+        self,
+        lease_duration_in_seconds: u64,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            holder_id: self.holder_id,
+            lease_duration_in_seconds,
+        }
+    }
+
+    pub async fn try_acquire(
+        &self,
+        name: &str,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        self.storage.try_acquire_leadership(name, self.holder_id, self.lease_duration_in_seconds).await
+    }
+
+    pub async fn release(
+        &self,
+        name: &str,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        self.storage.release_leadership(name, self.holder_id).await
+    }
+}
@@ -1,22 +1,82 @@
 pub mod aws;
+pub mod aws_data_key_provider;
+pub mod aws_secret_resolver;
+pub mod data_key_provider;
+pub mod destination_registry;
 pub mod environment;
 pub mod error;
 pub mod outbox_processor;
 
 mod app_state;
+pub mod cloud_events;
+pub mod expired_outbox_action;
+pub mod http_auth_profile;
+mod http_authenticator;
 pub mod http_destination;
 pub mod http_gateway;
 mod http_notification_service;
+pub mod leader_election;
+pub mod lock_strategy;
+pub mod named_destination;
 mod notification;
+pub mod oauth2_token_cache;
 pub mod outbox;
+pub mod outbox_backlog;
 mod outbox_cleaner_schedule;
+mod outbox_columns;
+mod outbox_copy;
 pub mod outbox_destination;
+pub mod outbox_event;
 mod outbox_group;
+pub mod outbox_insert_options;
+pub mod outbox_insert_result;
+pub mod outbox_job;
+pub mod outbox_job_repository;
 mod outbox_private_repository;
 pub mod outbox_repository;
 pub mod outbox_resources;
+pub mod outbox_storage;
+mod outbox_storage_backend;
+pub mod payload_compression;
+pub mod payload_encryptor;
+pub mod payload_envelope;
+pub mod payload_transform;
+mod postgres_outbox_storage;
+pub mod processing_mode;
+mod registered_destination;
+pub mod routing_rule;
+pub mod routing_table;
+pub mod schema_registry;
+pub mod schema_validator;
+pub mod secret_resolver;
+pub mod secret_resolvers;
 pub mod shutdown;
+pub mod singleton_job;
+pub mod singleton_job_registry;
 pub mod sns_destination;
 mod sns_notification_service;
 pub mod sqs_destination;
 mod sqs_notification_service;
+pub mod tenant_fairness;
+pub mod worker_sharding;
+
+#[cfg(feature = "derive")]
+pub use outbox_pattern_processor_derive::OutboxEvent;
+
+#[cfg(feature = "diesel")]
+pub mod diesel_outbox_repository;
+
+#[cfg(feature = "sea-orm")]
+pub mod sea_orm_outbox_repository;
+
+#[cfg(feature = "mysql")]
+pub mod mysql_outbox_repository;
+
+#[cfg(feature = "mysql")]
+mod mysql_outbox_storage;
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite_outbox_repository;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_outbox_storage;
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LockStrategy {
+    #[default]
+    LockTable,
+    AdvisoryLock,
+    PartitionTable,
+}
@@ -0,0 +1,718 @@
+use crate::app_state::AppState;
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::outbox_backlog::OutboxBacklog;
+use crate::outbox_cleaner_schedule::OutboxCleanerSchedule;
+use crate::outbox_job::OutboxJob;
+use crate::outbox_storage_backend::{ensure_supported_options, priority_fair_share_limit, push_ids, OutboxStorageBackend, STUCK_PARTITION_KEYS_LIMIT};
+use crate::registered_destination::RegisteredDestination;
+use async_trait::async_trait;
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::{MySql, Pool, QueryBuilder, Transaction};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::instrument;
+use uuid::Uuid;
+
+const LOCK_CANDIDATES_FACTOR: i64 = 2;
+
+pub struct MySqlOutboxStorage {
+    mysql_pool: Pool<MySql>,
+    lease: Mutex<Option<(Uuid, Vec<Uuid>)>>,
+}
+
+#[async_trait]
+impl OutboxStorageBackend for MySqlOutboxStorage {
+    #[instrument(skip_all, name = "lock_and_get_outboxes")]
+    async fn list(
+        &self,
+        app_state: &AppState,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        ensure_supported_options(app_state, "mysql")?;
+
+        let lock_id = Uuid::now_v7();
+
+        self.lock_partition_key(app_state, lock_id).await?;
+
+        let outboxes = self.get_outboxes_ranked_from_locked_partition_key(app_state, lock_id).await?;
+        *self.lease.lock().await = Some((lock_id, outboxes.iter().map(|it| it.partition_key).collect()));
+
+        Ok(outboxes)
+    }
+
+    #[instrument(skip_all)]
+    async fn renew_lease(
+        &self,
+        app_state: &AppState,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let (lock_id, partition_keys) = match self.lease.lock().await.clone() {
+            Some(lease) if !lease.1.is_empty() => lease,
+            _ => return Ok(true),
+        };
+
+        let mut query_builder = QueryBuilder::new("update outbox_lock set processing_until = timestampadd(second, ");
+        query_builder.push_bind(app_state.max_in_flight_interval_in_seconds.unwrap_or(30) as i64);
+        query_builder.push(", now(6)) where processed_at is null and lock_id = ");
+        query_builder.push_bind(lock_id);
+        query_builder.push(" and partition_key in ");
+        push_ids(&mut query_builder, &partition_keys);
+
+        let result = query_builder
+            .build()
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to renew partition keys lease"))?;
+
+        Ok(result.rows_affected() as usize == partition_keys.len())
+    }
+
+    #[instrument(skip_all)]
+    async fn complete(
+        &self,
+        app_state: &AppState,
+        successfully_outboxes: &[Outbox],
+        failure_outboxes: &[Outbox],
+        invalid_outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut transaction = self.begin_transaction().await?;
+
+        if app_state.delete_after_process_successfully.unwrap_or(false) {
+            Self::delete_processed(app_state, &mut transaction, successfully_outboxes).await?;
+        } else {
+            Self::mark_as_processed(app_state, &mut transaction, successfully_outboxes).await?;
+        }
+
+        if !failure_outboxes.is_empty() {
+            Self::increase_attempts(app_state, &mut transaction, failure_outboxes).await?;
+        }
+
+        if !invalid_outboxes.is_empty() {
+            Self::dead_letter(app_state, &mut transaction, invalid_outboxes).await?;
+        }
+
+        Self::commit_transaction(transaction).await
+    }
+
+    #[instrument(skip_all)]
+    async fn find_registered_destinations(&self) -> Result<Vec<RegisteredDestination>, OutboxPatternProcessorError> {
+        let sql = "select name, destination from outbox_destination";
+
+        sqlx::query_as(sql)
+            .fetch_all(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list registered destinations"))
+    }
+
+    #[instrument(skip_all)]
+    async fn clear_processed_locked_partition_key(&self) -> Result<(), OutboxPatternProcessorError> {
+        let mut transaction = self.begin_transaction().await?;
+
+        if let Some(outbox_clear_schedule) = Self::find_cleaner_schedule(&mut transaction).await? {
+            if outbox_clear_schedule.is_due() {
+                Self::clear_processed_locks(&mut transaction).await?;
+                Self::update_last_cleaner_execution(&mut transaction).await?;
+                Self::commit_transaction(transaction).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn try_acquire_leadership(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+        lease_duration_in_seconds: u64,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let sql_register = "insert ignore into outbox_leader (name, holder_id, lease_until) values (?, ?, timestampadd(second, ?, now(6)))";
+
+        sqlx::query(sql_register)
+            .bind(name)
+            .bind(holder_id)
+            .bind(lease_duration_in_seconds as i64)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to acquire leadership"))?;
+
+        let sql_acquire = "update outbox_leader set holder_id = ?, lease_until = timestampadd(second, ?, now(6)) where name = ? and (holder_id = ? or lease_until <= now(6))";
+
+        sqlx::query(sql_acquire)
+            .bind(holder_id)
+            .bind(lease_duration_in_seconds as i64)
+            .bind(name)
+            .bind(holder_id)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to acquire leadership"))?;
+
+        let leader: Option<Uuid> = sqlx::query_scalar("select holder_id from outbox_leader where name = ?")
+            .bind(name)
+            .fetch_optional(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to acquire leadership"))?;
+
+        Ok(leader == Some(holder_id))
+    }
+
+    #[instrument(skip_all)]
+    async fn release_leadership(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        sqlx::query("update outbox_leader set lease_until = now(6) where name = ? and holder_id = ?")
+            .bind(name)
+            .bind(holder_id)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to release leadership"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn find_leadership_last_run_at(
+        &self,
+        name: &str,
+    ) -> Result<Option<DateTime<Utc>>, OutboxPatternProcessorError> {
+        let last_run_at: Option<Option<DateTime<Utc>>> = sqlx::query_scalar("select last_run_at from outbox_leader where name = ?")
+            .bind(name)
+            .fetch_optional(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to find leadership last run name={name}")))?;
+
+        Ok(last_run_at.flatten())
+    }
+
+    #[instrument(skip_all)]
+    async fn record_leadership_run(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+        last_run_at: DateTime<Utc>,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        sqlx::query("update outbox_leader set last_run_at = ? where name = ? and holder_id = ?")
+            .bind(last_run_at)
+            .bind(name)
+            .bind(holder_id)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to record leadership run name={name}")))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn purge_processed_locks(&self) -> Result<u64, OutboxPatternProcessorError> {
+        let sql = "delete from outbox_lock where processed_at is not null and processed_at < now(6)";
+
+        let result = sqlx::query(sql)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to clear processed locks"))?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip_all)]
+    async fn purge_processed_outboxes(
+        &self,
+        retention_in_seconds: u64,
+    ) -> Result<u64, OutboxPatternProcessorError> {
+        let sql = "delete from outbox where processed_at is not null and processed_at < timestampadd(second, ?, now(6))";
+
+        let result = sqlx::query(sql)
+            .bind(-(retention_in_seconds as i64))
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to delete processed outboxes"))?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip_all)]
+    async fn find_backlog(
+        &self,
+        outbox_failure_limit: u32,
+    ) -> Result<OutboxBacklog, OutboxPatternProcessorError> {
+        let sql = r#"select
+    cast(coalesce(sum(attempts < ? and dead_lettered_at is null), 0) as signed) as pending,
+    cast(coalesce(sum(attempts >= ? or dead_lettered_at is not null), 0) as signed) as dead_lettered,
+    min(case when attempts < ? and dead_lettered_at is null then process_after end) as oldest_pending_at
+from outbox
+where processed_at is null"#;
+
+        sqlx::query_as(sql)
+            .bind(outbox_failure_limit as i32)
+            .bind(outbox_failure_limit as i32)
+            .bind(outbox_failure_limit as i32)
+            .fetch_one(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to find outbox backlog"))
+    }
+
+    #[instrument(skip_all)]
+    async fn find_stuck_partition_keys(
+        &self,
+        outbox_failure_limit: u32,
+        threshold_in_seconds: u64,
+    ) -> Result<Vec<Uuid>, OutboxPatternProcessorError> {
+        let sql = r#"select partition_key
+from outbox
+where processed_at is null
+    and attempts < ?
+    and dead_lettered_at is null
+group by partition_key
+having min(process_after) < timestampadd(second, ?, now(6))
+order by min(process_after)
+limit ?"#;
+
+        sqlx::query_scalar(sql)
+            .bind(outbox_failure_limit as i32)
+            .bind(-(threshold_in_seconds as i64))
+            .bind(STUCK_PARTITION_KEYS_LIMIT)
+            .fetch_all(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to find stuck partition keys"))
+    }
+
+    #[instrument(skip_all)]
+    async fn save_backlog_snapshot(
+        &self,
+        backlog: &OutboxBacklog,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "insert into outbox_backlog_snapshot (pending, dead_lettered, oldest_pending_at) values (?, ?, ?)";
+
+        sqlx::query(sql)
+            .bind(backlog.pending)
+            .bind(backlog.dead_lettered)
+            .bind(backlog.oldest_pending_at)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to save outbox backlog snapshot"))?;
+
+        Ok(())
+    }
+
+    async fn find_vacuum_analyze_hints(
+        &self,
+        _dead_tuple_ratio: f64,
+    ) -> Result<Vec<String>, OutboxPatternProcessorError> {
+        Err(OutboxPatternProcessorError::new(
+            "VACUUM and ANALYZE hints are only available for Postgres",
+            "Failed to find outbox tables statistics",
+        ))
+    }
+
+    #[instrument(skip_all)]
+    async fn find_jobs(&self) -> Result<Vec<OutboxJob>, OutboxPatternProcessorError> {
+        sqlx::query_as("select * from outbox_job order by name")
+            .fetch_all(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list outbox jobs"))
+    }
+
+    #[instrument(skip_all)]
+    async fn save_job(
+        &self,
+        job: &OutboxJob,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = r#"insert into outbox_job (name, kind, cron_expression, enabled)
+values (?, ?, ?, ?)
+on duplicate key update kind = values(kind), cron_expression = values(cron_expression), enabled = values(enabled)"#;
+
+        sqlx::query(sql)
+            .bind(&job.name)
+            .bind(&job.kind)
+            .bind(&job.cron_expression)
+            .bind(job.enabled)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to save outbox job name={}", job.name)))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn delete_job(
+        &self,
+        name: &str,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let result = sqlx::query("delete from outbox_job where name = ?")
+            .bind(name)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to delete outbox job name={name}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip_all)]
+    async fn record_job_run(
+        &self,
+        name: &str,
+        last_run_at: DateTime<Utc>,
+        last_result: &str,
+        last_duration_in_millis: i64,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "update outbox_job set last_run_at = ?, last_result = ?, last_duration_in_millis = ? where name = ?";
+
+        sqlx::query(sql)
+            .bind(last_run_at)
+            .bind(last_result)
+            .bind(last_duration_in_millis)
+            .bind(name)
+            .execute(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to record outbox job run name={name}")))?;
+
+        Ok(())
+    }
+}
+
+impl MySqlOutboxStorage {
+    pub fn new(mysql_pool: Pool<MySql>) -> Self {
+        Self {
+            mysql_pool,
+            lease: Mutex::new(None),
+        }
+    }
+
+    async fn begin_transaction(&self) -> Result<Transaction<'_, MySql>, OutboxPatternProcessorError> {
+        self.mysql_pool
+            .begin()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to create transaction"))
+    }
+
+    async fn commit_transaction(transaction: Transaction<'_, MySql>) -> Result<(), OutboxPatternProcessorError> {
+        transaction
+            .commit()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to commit transaction"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn get_outboxes_ranked_from_locked_partition_key(
+        &self,
+        app_state: &AppState,
+        lock_id: Uuid,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        let sql_list = r#"with locked as (
+    select
+        o.idempotent_key,
+        row_number() over (partition by o.partition_key order by o.process_after asc) as rnk
+    from outbox o
+    inner join outbox_lock ol on o.partition_key = ol.partition_key
+    where o.process_after < now(6)
+        and o.processed_at is null
+        and o.attempts < ?
+        and o.dead_lettered_at is null
+        and ol.lock_id = ?
+)
+select o.*
+from outbox o
+inner join locked l on o.idempotent_key = l.idempotent_key
+where l.rnk = 1"#;
+
+        sqlx::query_as(sql_list)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .bind(lock_id)
+            .fetch_all(&self.mysql_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list outboxes"))
+    }
+
+    #[instrument(skip_all)]
+    async fn lock_partition_key(
+        &self,
+        app_state: &AppState,
+        lock_id: Uuid,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let query_limit = app_state.outbox_query_limit.unwrap_or(50) as usize;
+
+        let mut transaction = self.begin_transaction().await?;
+
+        let mut partition_keys = vec![];
+        for (partition_key, idempotent_key) in Self::find_lock_candidates(app_state, &mut transaction).await? {
+            if partition_keys.len() >= query_limit {
+                break;
+            }
+
+            if Self::try_lock_head(&mut transaction, idempotent_key).await? {
+                partition_keys.push(partition_key);
+            }
+        }
+
+        if partition_keys.is_empty() {
+            return Self::commit_transaction(transaction).await;
+        }
+
+        let processing_until_incremente_interval = app_state.max_in_flight_interval_in_seconds.unwrap_or(30) as i64;
+
+        let mut query_builder = QueryBuilder::new("insert into outbox_lock (partition_key, lock_id, processing_until) ");
+        query_builder.push_values(partition_keys, |mut b, partition_key| {
+            b.push_bind(partition_key)
+                .push_bind(lock_id)
+                .push("timestampadd(second, ")
+                .push_bind_unseparated(processing_until_incremente_interval)
+                .push_unseparated(", now(6))");
+        });
+        query_builder.push(" on duplicate key update lock_id = outbox_lock.lock_id");
+
+        query_builder
+            .build()
+            .execute(&mut *transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to lock outboxes"))?;
+
+        Self::commit_transaction(transaction).await
+    }
+
+    #[instrument(skip_all)]
+    async fn find_lock_candidates(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<Vec<(Uuid, Uuid)>, OutboxPatternProcessorError> {
+        let sql_candidates = r#"select heads.partition_key, heads.idempotent_key
+from (
+    select
+        ranked.partition_key,
+        ranked.idempotent_key,
+        ranked.process_after,
+        ranked.priority,
+        row_number() over (order by ranked.process_after) as age_rank
+    from (
+        select
+            o.partition_key,
+            o.idempotent_key,
+            o.process_after,
+            max(o.priority) over (partition by o.partition_key) as priority,
+            row_number() over (partition by o.partition_key order by o.process_after asc) as rnk
+        from outbox o
+        where o.processed_at is null
+            and o.process_after < now(6)
+            and o.attempts < ?
+            and o.dead_lettered_at is null
+            and not exists (select 1 from outbox_lock ol where ol.partition_key = o.partition_key and ol.processed_at is null)
+    ) ranked
+    where ranked.rnk = 1
+) heads
+order by
+    case when heads.age_rank <= ? then 0 else 1 end,
+    heads.priority desc,
+    heads.process_after
+limit ?"#;
+
+        let query_limit = app_state.outbox_query_limit.unwrap_or(50) as i64;
+
+        sqlx::query_as(sql_candidates)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .bind(priority_fair_share_limit(app_state))
+            .bind(query_limit * LOCK_CANDIDATES_FACTOR)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to lock outboxes"))
+    }
+
+    #[instrument(skip_all)]
+    async fn try_lock_head(
+        transaction: &mut Transaction<'_, MySql>,
+        idempotent_key: Uuid,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let sql_lock = "select idempotent_key from outbox where idempotent_key = ? for update skip locked";
+
+        let locked: Option<Uuid> = sqlx::query_scalar(sql_lock)
+            .bind(idempotent_key)
+            .fetch_optional(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to lock outboxes"))?;
+
+        Ok(locked.is_some())
+    }
+
+    #[instrument(skip_all)]
+    async fn mark_as_processed(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, MySql>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut query_builder = QueryBuilder::new("update outbox set processed_at = now(6), attempts = attempts + 1 where idempotent_key in ");
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to mark outboxes as processed"))?;
+
+        Self::unlock(app_state, transaction, outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn delete_processed(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, MySql>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut query_builder = QueryBuilder::new("delete from outbox where idempotent_key in ");
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to delete processed outboxes"))?;
+
+        Self::unlock(app_state, transaction, outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn increase_attempts(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, MySql>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        if let Some(delay) = app_state.delay_for_failure_attempt_in_seconds {
+            if delay > 0 {
+                let mut query_builder = QueryBuilder::new("update outbox set process_after = ");
+                query_builder.push_bind(Utc::now() + Duration::from_secs(delay));
+                query_builder.push(" where processed_at is null and partition_key in ");
+                push_ids(&mut query_builder, &outboxes.iter().map(|it| it.partition_key).collect::<Vec<Uuid>>());
+
+                query_builder
+                    .build()
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to increase attempts"))?;
+            }
+        }
+
+        let mut query_builder = QueryBuilder::new("update outbox set attempts = attempts + 1 where idempotent_key in ");
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to increase attempts"))?;
+
+        Self::unlock(app_state, transaction, outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn dead_letter(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, MySql>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut query_builder = QueryBuilder::new("update outbox set dead_lettered_at = now(6), attempts = attempts + 1 where idempotent_key in ");
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to dead letter outboxes"))?;
+
+        Self::unlock(app_state, transaction, outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn unlock(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, MySql>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        Self::unlock_by_partition_key(app_state, transaction, outboxes).await?;
+        Self::unlock_expired_processes(app_state, transaction).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn unlock_expired_processes(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql_unlock_by_processing_until = if app_state.scheduled_clear_locked_partition.unwrap_or(false) {
+            "update outbox_lock set processed_at = now(6) where processing_until < now(6)"
+        } else {
+            "delete from outbox_lock where processing_until < now(6)"
+        };
+
+        sqlx::query(sql_unlock_by_processing_until)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to unlock partition keys"))?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn unlock_by_partition_key(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, MySql>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql_unlock_by_partition_key = if app_state.scheduled_clear_locked_partition.unwrap_or(false) {
+            "update outbox_lock set processed_at = now(6) where processed_at is null and partition_key in "
+        } else {
+            "delete from outbox_lock where processed_at is null and partition_key in "
+        };
+
+        let mut query_builder = QueryBuilder::new(sql_unlock_by_partition_key);
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.partition_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to unlock partition keys"))?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn clear_processed_locks(transaction: &mut Transaction<'_, MySql>) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "delete from outbox_lock where processed_at is not null and processed_at < now(6)";
+
+        sqlx::query(sql)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to clear processed locks"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn find_cleaner_schedule(transaction: &mut Transaction<'_, MySql>) -> Result<Option<OutboxCleanerSchedule>, OutboxPatternProcessorError> {
+        let sql = "select cron_expression, last_execution from outbox_cleaner_schedule limit 1 for update skip locked";
+
+        sqlx::query_as(sql)
+            .fetch_optional(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to update last cleaner execution"))
+    }
+
+    #[instrument(skip_all)]
+    async fn update_last_cleaner_execution(transaction: &mut Transaction<'_, MySql>) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "update outbox_cleaner_schedule set last_execution = now(6)";
+
+        sqlx::query(sql)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to update last cleaner execution"))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Deserialize, Serialize, Debug, FromRow, Clone, PartialEq)]
+pub struct NamedDestination {
+    pub name: String,
+}
@@ -0,0 +1,45 @@
+use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OAuth2Token {
+    pub access_token: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl OAuth2Token {
+    pub fn is_valid(&self) -> bool {
+        self.expires_at.map(|expires_at| expires_at > Utc::now()).unwrap_or(true)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct OAuth2TokenCache {
+    tokens: Arc<Mutex<HashMap<String, OAuth2Token>>>,
+}
+
+impl OAuth2TokenCache {
+    pub async fn get(
+        &self,
+        profile_name: &str,
+    ) -> Option<OAuth2Token> {
+        self.tokens.lock().await.get(profile_name).filter(|token| token.is_valid()).cloned()
+    }
+
+    pub async fn put(
+        &self,
+        profile_name: &str,
+        token: OAuth2Token,
+    ) {
+        self.tokens.lock().await.insert(profile_name.to_string(), token);
+    }
+
+    pub async fn invalidate(
+        &self,
+        profile_name: &str,
+    ) {
+        self.tokens.lock().await.remove(profile_name);
+    }
+}
@@ -1,7 +1,11 @@
+use crate::error::OutboxPatternProcessorError;
 use crate::http_destination::HttpDestination;
 use crate::outbox_destination::OutboxDestination;
+use crate::payload_compression::PayloadCompression;
+use crate::payload_envelope::PayloadEnvelope;
 use crate::sns_destination::SnsDestination;
 use crate::sqs_destination::SqsDestination;
+use base64::prelude::{Engine, BASE64_STANDARD};
 use serde_json::Value;
 use sqlx::types::chrono::{DateTime, Utc};
 use sqlx::types::Json;
@@ -9,6 +13,8 @@ use sqlx::FromRow;
 use std::collections::HashMap;
 use uuid::Uuid;
 
+const TEXT_PAYLOAD_ENCODING_SUFFIX: &str = ";text";
+
 #[derive(Debug, FromRow, Clone, PartialEq)]
 pub struct Outbox {
     pub idempotent_key: Uuid,
@@ -16,10 +22,20 @@ pub struct Outbox {
     pub destinations: Json<Vec<OutboxDestination>>,
     pub headers: Option<Json<HashMap<String, String>>>,
     pub payload: String,
+    pub payload_binary: Option<Vec<u8>>,
+    pub content_type: Option<String>,
+    pub content_encoding: Option<String>,
+    pub encryption: Option<Json<PayloadEnvelope>>,
     pub attempts: i32,
     pub created_at: DateTime<Utc>,
     pub process_after: Option<DateTime<Utc>>,
     pub processed_at: Option<DateTime<Utc>>,
+    pub priority: i32,
+    pub tenant: Option<String>,
+    pub unordered: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub dead_lettered_at: Option<DateTime<Utc>>,
+    pub payload_json: Option<Json<Value>>,
 }
 
 impl Outbox {
@@ -33,13 +49,61 @@ impl Outbox {
             destinations: self.destinations.clone(),
             headers: self.headers.clone(),
             payload: self.payload.clone(),
+            payload_binary: self.payload_binary.clone(),
+            content_type: self.content_type.clone(),
+            content_encoding: self.content_encoding.clone(),
+            encryption: self.encryption.clone(),
             attempts: self.attempts,
             created_at: self.created_at,
             process_after: Some(process_after),
             processed_at: self.processed_at,
+            priority: self.priority,
+            tenant: self.tenant.clone(),
+            unordered: self.unordered,
+            expires_at: self.expires_at,
+            dead_lettered_at: self.dead_lettered_at,
+            payload_json: self.payload_json.clone(),
+        }
+    }
+
+    pub fn with_priority(
+        self,
+        priority: i32,
+    ) -> Self {
+        Outbox { priority, ..self }
+    }
+
+    pub fn with_tenant(
+        self,
+        tenant: &str,
+    ) -> Self {
+        Outbox {
+            tenant: Some(tenant.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_unordered(
+        self,
+        unordered: bool,
+    ) -> Self {
+        Outbox { unordered, ..self }
+    }
+
+    pub fn with_expires_at(
+        self,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Outbox {
+            expires_at: Some(expires_at),
+            ..self
         }
     }
 
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.map(|expires_at| expires_at <= Utc::now()).unwrap_or(false)
+    }
+
     pub fn http_post_json(
         partition_key: Uuid,
         url: &str,
@@ -73,7 +137,12 @@ impl Outbox {
         headers: Option<HashMap<String, String>>,
         payload: &str,
     ) -> Self {
-        let destinations = vec![OutboxDestination::SqsDestination(SqsDestination { queue_url: queue_url.to_string() })];
+        let destinations = vec![OutboxDestination::SqsDestination(SqsDestination {
+            queue_url: queue_url.to_string(),
+            transforms: None,
+            cloud_events: None,
+            compression: None,
+        })];
 
         Self::new(partition_key, destinations, headers, payload)
     }
@@ -84,7 +153,12 @@ impl Outbox {
         headers: Option<HashMap<String, String>>,
         payload: &str,
     ) -> Self {
-        let destinations = vec![OutboxDestination::SnsDestination(SnsDestination { topic_arn: topic_arn.to_string() })];
+        let destinations = vec![OutboxDestination::SnsDestination(SnsDestination {
+            topic_arn: topic_arn.to_string(),
+            transforms: None,
+            cloud_events: None,
+            compression: None,
+        })];
 
         Self::new(partition_key, destinations, headers, payload)
     }
@@ -96,7 +170,12 @@ impl Outbox {
         headers: Option<HashMap<String, String>>,
         payload: &Value,
     ) -> Self {
-        let destinations = vec![OutboxDestination::SnsDestination(SnsDestination { topic_arn: topic_arn.to_string() })];
+        let destinations = vec![OutboxDestination::SnsDestination(SnsDestination {
+            topic_arn: topic_arn.to_string(),
+            transforms: None,
+            cloud_events: None,
+            compression: None,
+        })];
         Self::http(partition_key, url, headers, destinations, &payload.to_string(), None)
     }
 
@@ -107,7 +186,12 @@ impl Outbox {
         headers: Option<HashMap<String, String>>,
         payload: &Value,
     ) -> Self {
-        let destinations = vec![OutboxDestination::SqsDestination(SqsDestination { queue_url: queue_url.to_string() })];
+        let destinations = vec![OutboxDestination::SqsDestination(SqsDestination {
+            queue_url: queue_url.to_string(),
+            transforms: None,
+            cloud_events: None,
+            compression: None,
+        })];
         Self::http(partition_key, url, headers, destinations, &payload.to_string(), None)
     }
 
@@ -128,11 +212,146 @@ impl Outbox {
             url: url.to_string(),
             headers: Some(extended_headers),
             method,
+            auth: None,
+            transforms: None,
+            cloud_events: None,
+            compression: None,
         }));
 
         Self::new(partition_key, all_destinations, None, payload)
     }
 
+    pub fn binary(
+        partition_key: Uuid,
+        destinations: Vec<OutboxDestination>,
+        headers: Option<HashMap<String, String>>,
+        payload: &[u8],
+        content_type: &str,
+    ) -> Self {
+        Outbox {
+            payload_binary: Some(payload.to_vec()),
+            content_type: Some(content_type.to_string()),
+            ..Self::new(partition_key, destinations, headers, "")
+        }
+    }
+
+    pub fn json(
+        partition_key: Uuid,
+        destinations: Vec<OutboxDestination>,
+        headers: Option<HashMap<String, String>>,
+        payload: &Value,
+    ) -> Self {
+        Outbox {
+            payload_json: Some(Json(payload.clone())),
+            ..Self::new(partition_key, destinations, headers, "")
+        }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        self.payload_binary.is_some()
+    }
+
+    pub fn body_bytes(&self) -> Vec<u8> {
+        match (&self.payload_binary, &self.payload_json) {
+            (Some(bytes), _) => bytes.clone(),
+            (None, Some(payload_json)) => payload_json.0.to_string().into_bytes(),
+            (None, None) => self.payload.clone().into_bytes(),
+        }
+    }
+
+    pub fn encoded_payload(&self) -> String {
+        match (&self.payload_binary, &self.payload_json) {
+            (Some(bytes), _) => BASE64_STANDARD.encode(bytes),
+            (None, Some(payload_json)) => payload_json.0.to_string(),
+            (None, None) => self.payload.clone(),
+        }
+    }
+
+    pub(crate) fn message_attributes(&self) -> HashMap<String, String> {
+        let mut attributes = self.headers.clone().map(|headers| headers.0).unwrap_or_default();
+        if let Some(content_type) = &self.content_type {
+            if !attributes.keys().any(|key| key.eq_ignore_ascii_case("content-type")) {
+                attributes.insert("content-type".to_string(), content_type.clone());
+            }
+        }
+        if self.is_binary() {
+            attributes.insert("content-transfer-encoding".to_string(), "base64".to_string());
+        }
+        if let Some(content_encoding) = &self.content_encoding {
+            attributes.insert("content-encoding".to_string(), content_encoding.clone());
+        }
+
+        attributes
+    }
+
+    pub fn expand_json_payload(&self) -> Self {
+        match &self.payload_json {
+            Some(payload_json) => Outbox {
+                payload: payload_json.0.to_string(),
+                payload_json: None,
+                ..self.clone()
+            },
+            None => self.clone(),
+        }
+    }
+
+    pub fn compress(
+        &self,
+        compression: PayloadCompression,
+    ) -> Result<Self, OutboxPatternProcessorError> {
+        if self.content_encoding.is_some() {
+            return Ok(self.clone());
+        }
+
+        let content_encoding = if self.is_binary() {
+            compression.content_encoding().to_string()
+        } else {
+            format!("{}{TEXT_PAYLOAD_ENCODING_SUFFIX}", compression.content_encoding())
+        };
+
+        Ok(Outbox {
+            payload: String::new(),
+            payload_binary: Some(compression.compress(&self.body_bytes())?),
+            payload_json: None,
+            content_encoding: Some(content_encoding),
+            ..self.clone()
+        })
+    }
+
+    pub fn decompress(&self) -> Result<Self, OutboxPatternProcessorError> {
+        let (compression, is_text) = match &self.content_encoding {
+            Some(content_encoding) => match content_encoding.strip_suffix(TEXT_PAYLOAD_ENCODING_SUFFIX) {
+                Some(text_content_encoding) => (PayloadCompression::from_content_encoding(text_content_encoding)?, true),
+                None => (PayloadCompression::from_content_encoding(content_encoding)?, false),
+            },
+            None => return Ok(self.clone()),
+        };
+
+        let decompressed = compression.decompress(&self.body_bytes())?;
+
+        if !is_text {
+            return Ok(Outbox {
+                payload_binary: Some(decompressed),
+                content_encoding: None,
+                ..self.clone()
+            });
+        }
+
+        let payload = String::from_utf8(decompressed).map_err(|error| {
+            OutboxPatternProcessorError::new(
+                &error.to_string(),
+                &format!("Failed to decode decompressed payload for outbox idempotent_key={}", self.idempotent_key),
+            )
+        })?;
+
+        Ok(Outbox {
+            payload,
+            payload_binary: None,
+            content_encoding: None,
+            ..self.clone()
+        })
+    }
+
     pub fn new(
         partition_key: Uuid,
         destinations: Vec<OutboxDestination>,
@@ -145,10 +364,20 @@ impl Outbox {
             destinations: Json(destinations),
             headers: headers.map(Json),
             payload: payload.to_string(),
+            payload_binary: None,
+            content_type: None,
+            content_encoding: None,
+            encryption: None,
             attempts: 0,
             created_at: Utc::now(),
             process_after: None,
             processed_at: None,
+            priority: 0,
+            tenant: None,
+            unordered: false,
+            expires_at: None,
+            dead_lettered_at: None,
+            payload_json: None,
         }
     }
 }
@@ -0,0 +1,9 @@
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow, Clone, PartialEq)]
+pub struct OutboxBacklog {
+    pub pending: i64,
+    pub dead_lettered: i64,
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+}
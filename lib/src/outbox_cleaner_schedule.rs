@@ -1,8 +1,22 @@
+use cron::Schedule;
 use sqlx::types::chrono::{DateTime, Utc};
 use sqlx::FromRow;
+use std::str::FromStr;
 
 #[derive(Debug, FromRow, Clone, PartialEq)]
 pub struct OutboxCleanerSchedule {
     pub cron_expression: String,
     pub last_execution: DateTime<Utc>,
 }
+
+impl OutboxCleanerSchedule {
+    pub fn is_due(&self) -> bool {
+        if let Ok(schedule) = Schedule::from_str(&self.cron_expression) {
+            if let Some(next_execution) = schedule.after(&self.last_execution).next() {
+                return (next_execution - Utc::now()).num_seconds() <= 0;
+            }
+        }
+
+        false
+    }
+}
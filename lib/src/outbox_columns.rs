@@ -0,0 +1,93 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::types::chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub const OUTBOX_INSERT_COLUMNS: [&str; 16] = [
+    "idempotent_key",
+    "partition_key",
+    "destinations",
+    "headers",
+    "payload",
+    "payload_binary",
+    "content_type",
+    "content_encoding",
+    "encryption",
+    "created_at",
+    "process_after",
+    "priority",
+    "tenant",
+    "unordered",
+    "expires_at",
+    "payload_json",
+];
+
+pub const OUTBOX_INSERT_MAX_ROWS: usize = u16::MAX as usize / OUTBOX_INSERT_COLUMNS.len();
+
+pub struct OutboxColumns {
+    pub idempotent_key: Uuid,
+    pub partition_key: Uuid,
+    pub destinations: Value,
+    pub headers: Option<Value>,
+    pub payload: String,
+    pub payload_binary: Option<Vec<u8>>,
+    pub content_type: Option<String>,
+    pub content_encoding: Option<String>,
+    pub encryption: Option<Value>,
+    pub created_at: DateTime<Utc>,
+    pub process_after: DateTime<Utc>,
+    pub priority: i32,
+    pub tenant: Option<String>,
+    pub unordered: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub payload_json: Option<Value>,
+}
+
+impl OutboxColumns {
+    pub fn from_outbox(outbox: &Outbox) -> Result<Self, OutboxPatternProcessorError> {
+        Ok(Self {
+            idempotent_key: outbox.idempotent_key,
+            partition_key: outbox.partition_key,
+            destinations: Self::json(&outbox.destinations.0)?,
+            headers: outbox.headers.as_ref().map(|headers| Self::json(&headers.0)).transpose()?,
+            payload: outbox.payload.clone(),
+            payload_binary: outbox.payload_binary.clone(),
+            content_type: outbox.content_type.clone(),
+            content_encoding: outbox.content_encoding.clone(),
+            encryption: outbox.encryption.as_ref().map(|encryption| Self::json(&encryption.0)).transpose()?,
+            created_at: outbox.created_at,
+            process_after: outbox.process_after.unwrap_or(outbox.created_at),
+            priority: outbox.priority,
+            tenant: outbox.tenant.clone(),
+            unordered: outbox.unordered,
+            expires_at: outbox.expires_at,
+            payload_json: outbox.payload_json.as_ref().map(|payload_json| payload_json.0.clone()),
+        })
+    }
+
+    pub fn names() -> String {
+        OUTBOX_INSERT_COLUMNS.join(", ")
+    }
+
+    #[cfg(any(feature = "diesel", feature = "sea-orm"))]
+    pub fn insert_statement(rows: usize) -> String {
+        let values = (0..rows)
+            .map(|row| {
+                let placeholders = (1..=OUTBOX_INSERT_COLUMNS.len())
+                    .map(|column| format!("${}", row * OUTBOX_INSERT_COLUMNS.len() + column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("({placeholders})")
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("INSERT INTO outbox ({}) VALUES {values}", Self::names())
+    }
+
+    fn json<T: Serialize>(value: &T) -> Result<Value, OutboxPatternProcessorError> {
+        serde_json::to_value(value).map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to serialize outbox json column"))
+    }
+}
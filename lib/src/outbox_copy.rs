@@ -0,0 +1,73 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::outbox_columns::{OutboxColumns, OUTBOX_INSERT_COLUMNS};
+use serde_json::Value;
+use sqlx::types::chrono::{DateTime, Utc};
+
+const SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+const JSONB_VERSION: u8 = 1;
+const POSTGRES_EPOCH_IN_MICROS: i64 = 946_684_800_000_000;
+
+pub struct OutboxCopyEncoder;
+
+impl OutboxCopyEncoder {
+    pub fn statement() -> String {
+        format!("COPY outbox ({}) FROM STDIN WITH (FORMAT binary)", OutboxColumns::names())
+    }
+
+    pub fn encode(outboxes: &[Outbox]) -> Result<Vec<u8>, OutboxPatternProcessorError> {
+        let mut buffer = vec![];
+        buffer.extend_from_slice(SIGNATURE);
+        buffer.extend_from_slice(&0_i32.to_be_bytes());
+        buffer.extend_from_slice(&0_i32.to_be_bytes());
+
+        for outbox in outboxes {
+            let columns = OutboxColumns::from_outbox(outbox)?;
+
+            buffer.extend_from_slice(&(OUTBOX_INSERT_COLUMNS.len() as i16).to_be_bytes());
+            Self::field(&mut buffer, Some(columns.idempotent_key.as_bytes()));
+            Self::field(&mut buffer, Some(columns.partition_key.as_bytes()));
+            Self::field(&mut buffer, Some(&Self::jsonb(&columns.destinations)));
+            Self::field(&mut buffer, columns.headers.as_ref().map(Self::jsonb).as_deref());
+            Self::field(&mut buffer, Some(columns.payload.as_bytes()));
+            Self::field(&mut buffer, columns.payload_binary.as_deref());
+            Self::field(&mut buffer, columns.content_type.as_ref().map(|value| value.as_bytes()));
+            Self::field(&mut buffer, columns.content_encoding.as_ref().map(|value| value.as_bytes()));
+            Self::field(&mut buffer, columns.encryption.as_ref().map(Self::jsonb).as_deref());
+            Self::field(&mut buffer, Some(&Self::timestamptz(columns.created_at)));
+            Self::field(&mut buffer, Some(&Self::timestamptz(columns.process_after)));
+            Self::field(&mut buffer, Some(&columns.priority.to_be_bytes()));
+            Self::field(&mut buffer, columns.tenant.as_ref().map(|value| value.as_bytes()));
+            Self::field(&mut buffer, Some(&[columns.unordered as u8]));
+            Self::field(&mut buffer, columns.expires_at.map(Self::timestamptz).as_ref().map(|value| value.as_slice()));
+            Self::field(&mut buffer, columns.payload_json.as_ref().map(Self::jsonb).as_deref());
+        }
+
+        buffer.extend_from_slice(&(-1_i16).to_be_bytes());
+
+        Ok(buffer)
+    }
+
+    fn field(
+        buffer: &mut Vec<u8>,
+        value: Option<&[u8]>,
+    ) {
+        match value {
+            Some(bytes) => {
+                buffer.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+                buffer.extend_from_slice(bytes);
+            },
+            None => buffer.extend_from_slice(&(-1_i32).to_be_bytes()),
+        }
+    }
+
+    fn jsonb(value: &Value) -> Vec<u8> {
+        let mut bytes = vec![JSONB_VERSION];
+        bytes.extend_from_slice(value.to_string().as_bytes());
+        bytes
+    }
+
+    fn timestamptz(value: DateTime<Utc>) -> [u8; 8] {
+        (value.timestamp_micros() - POSTGRES_EPOCH_IN_MICROS).to_be_bytes()
+    }
+}
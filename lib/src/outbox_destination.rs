@@ -1,4 +1,5 @@
 use crate::http_destination::HttpDestination;
+use crate::named_destination::NamedDestination;
 use crate::sns_destination::SnsDestination;
 use crate::sqs_destination::SqsDestination;
 use serde::{Deserialize, Serialize};
@@ -9,4 +10,5 @@ pub enum OutboxDestination {
     SqsDestination(SqsDestination),
     SnsDestination(SnsDestination),
     HttpDestination(HttpDestination),
+    NamedDestination(NamedDestination),
 }
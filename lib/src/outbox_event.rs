@@ -0,0 +1,41 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::outbox_destination::OutboxDestination;
+use serde::Serialize;
+use std::collections::HashMap;
+pub use uuid::Uuid;
+
+pub const EVENT_TYPE_HEADER: &str = "x-event-type";
+pub const EVENT_VERSION_HEADER: &str = "x-event-version";
+
+pub trait OutboxEvent: Serialize {
+    fn event_type(&self) -> String;
+
+    fn event_version(&self) -> String {
+        "1".to_string()
+    }
+
+    fn partition_key(&self) -> Uuid;
+
+    fn destinations(&self) -> Vec<OutboxDestination> {
+        vec![]
+    }
+
+    fn headers(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+}
+
+impl Outbox {
+    pub fn event<E: OutboxEvent>(event: &E) -> Result<Self, OutboxPatternProcessorError> {
+        let payload = serde_json::to_string(event)
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to serialize event_type={}", event.event_type())))?;
+
+        let mut headers = event.headers();
+        headers.insert(EVENT_TYPE_HEADER.to_string(), event.event_type());
+        headers.insert(EVENT_VERSION_HEADER.to_string(), event.event_version());
+        headers.entry("content-type".to_string()).or_insert("application/json".to_string());
+
+        Ok(Self::new(event.partition_key(), event.destinations(), Some(headers), &payload))
+    }
+}
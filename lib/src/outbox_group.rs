@@ -1,3 +1,4 @@
+use crate::http_destination::HttpDestination;
 use crate::outbox::Outbox;
 use std::collections::HashMap;
 
@@ -5,5 +6,6 @@ use std::collections::HashMap;
 pub struct GroupedOutboxed {
     pub sqs: HashMap<String, Vec<Outbox>>,
     pub sns: HashMap<String, Vec<Outbox>>,
-    pub http: Vec<Outbox>,
+    pub http: Vec<(HttpDestination, Outbox)>,
+    pub failed: Vec<Outbox>,
 }
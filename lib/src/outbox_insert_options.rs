@@ -0,0 +1,84 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::payload_compression::PayloadCompression;
+use crate::payload_encryptor::PayloadEncryptor;
+use crate::schema_validator::SchemaValidator;
+
+#[derive(Clone, Default)]
+pub struct OutboxInsertOptions {
+    pub compression: Option<PayloadCompression>,
+    pub encryptor: Option<PayloadEncryptor>,
+    pub validator: Option<SchemaValidator>,
+}
+
+impl OutboxInsertOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_compression(
+        self,
+        compression: PayloadCompression,
+    ) -> Self {
+        Self {
+            compression: Some(compression),
+            encryptor: self.encryptor,
+            validator: self.validator,
+        }
+    }
+
+    pub fn with_encryptor(
+        self,
+        encryptor: PayloadEncryptor,
+    ) -> Self {
+        Self {
+            compression: self.compression,
+            encryptor: Some(encryptor),
+            validator: self.validator,
+        }
+    }
+
+    pub fn with_validator(
+        self,
+        validator: SchemaValidator,
+    ) -> Self {
+        Self {
+            compression: self.compression,
+            encryptor: self.encryptor,
+            validator: Some(validator),
+        }
+    }
+
+    pub(crate) async fn prepare(
+        &self,
+        outboxes: Vec<Outbox>,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        let mut prepared_outboxes = Vec::with_capacity(outboxes.len());
+
+        for outbox in outboxes {
+            if let Some(validator) = &self.validator {
+                let violations = validator.validate(&outbox).await?;
+                if !violations.is_empty() {
+                    return Err(OutboxPatternProcessorError::new(
+                        &violations.join("; "),
+                        &format!("Invalid payload for outbox idempotent_key={}", outbox.idempotent_key),
+                    ));
+                }
+            }
+
+            let outbox = match self.compression {
+                Some(compression) => outbox.compress(compression)?,
+                None => outbox,
+            };
+
+            let outbox = match &self.encryptor {
+                Some(encryptor) => encryptor.encrypt(&outbox).await?,
+                None => outbox,
+            };
+
+            prepared_outboxes.push(outbox);
+        }
+
+        Ok(prepared_outboxes)
+    }
+}
@@ -0,0 +1,7 @@
+use crate::outbox::Outbox;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutboxInsertResult {
+    pub inserted: Vec<Outbox>,
+    pub existing: Vec<Outbox>,
+}
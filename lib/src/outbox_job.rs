@@ -0,0 +1,68 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::singleton_job::SingletonJobKind;
+use cron::Schedule;
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use std::str::FromStr;
+
+#[derive(Debug, FromRow, Clone, PartialEq)]
+pub struct OutboxJob {
+    pub name: String,
+    pub kind: Json<SingletonJobKind>,
+    pub cron_expression: String,
+    pub enabled: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_result: Option<String>,
+    pub last_duration_in_millis: Option<i64>,
+}
+
+impl OutboxJob {
+    pub fn new(
+        name: &str,
+        kind: SingletonJobKind,
+        cron_expression: &str,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: Json(kind),
+            cron_expression: cron_expression.to_string(),
+            enabled: true,
+            last_run_at: None,
+            last_result: None,
+            last_duration_in_millis: None,
+        }
+    }
+
+    pub fn with_enabled(
+        self,
+        enabled: bool,
+    ) -> Self {
+        Self { enabled, ..self }
+    }
+
+    pub fn validate(&self) -> Result<(), OutboxPatternProcessorError> {
+        Schedule::from_str(&self.cron_expression)
+            .map(|_| ())
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Invalid cron expression for outbox job name={}", self.name)))
+    }
+
+    pub fn is_due(&self) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let last_run_at = match self.last_run_at {
+            None => return true,
+            Some(last_run_at) => last_run_at,
+        };
+
+        if let Ok(schedule) = Schedule::from_str(&self.cron_expression) {
+            if let Some(next_execution) = schedule.after(&last_run_at).next() {
+                return next_execution <= Utc::now();
+            }
+        }
+
+        false
+    }
+}
@@ -0,0 +1,59 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox_job::OutboxJob;
+use crate::outbox_storage::OutboxStorage;
+use crate::outbox_storage_backend::OutboxStorageBackend;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct OutboxJobRepository {
+    storage: Arc<dyn OutboxStorageBackend>,
+}
+
+impl OutboxJobRepository {
+    pub fn new(storage: impl Into<OutboxStorage>) -> Self {
+        Self {
+            storage: storage.into().backend(),
+        }
+    }
+
+    pub async fn find_all(&self) -> Result<Vec<OutboxJob>, OutboxPatternProcessorError> {
+        self.storage.find_jobs().await
+    }
+
+    pub async fn find_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<OutboxJob>, OutboxPatternProcessorError> {
+        Ok(self.storage.find_jobs().await?.into_iter().find(|it| it.name == name))
+    }
+
+    pub async fn save(
+        &self,
+        job: &OutboxJob,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        job.validate()?;
+        self.storage.save_job(job).await
+    }
+
+    pub async fn set_enabled(
+        &self,
+        name: &str,
+        enabled: bool,
+    ) -> Result<Option<OutboxJob>, OutboxPatternProcessorError> {
+        match self.find_by_name(name).await? {
+            None => Ok(None),
+            Some(job) => {
+                let job = job.with_enabled(enabled);
+                self.storage.save_job(&job).await?;
+                Ok(Some(job))
+            },
+        }
+    }
+
+    pub async fn delete(
+        &self,
+        name: &str,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        self.storage.delete_job(name).await
+    }
+}
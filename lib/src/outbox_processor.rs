@@ -1,21 +1,30 @@
 use crate::app_state::AppState;
+use crate::cloud_events::CloudEventsMode;
 use crate::error::OutboxPatternProcessorError;
+use crate::expired_outbox_action::ExpiredOutboxAction;
 use crate::http_gateway::HttpGateway;
 use crate::http_notification_service::HttpNotificationService;
+use crate::leader_election::LeaderElection;
+use crate::notification::NotificationResult;
 use crate::outbox::Outbox;
 use crate::outbox_destination::OutboxDestination;
 use crate::outbox_group::GroupedOutboxed;
-use crate::outbox_repository::OutboxRepository;
+use crate::outbox_job_repository::OutboxJobRepository;
 use crate::outbox_resources::OutboxProcessorResources;
+use crate::payload_compression::PayloadCompression;
+use crate::payload_transform::PayloadTransform;
+use crate::singleton_job::SingletonJobKind;
 use crate::sns_notification_service::SnsNotificationService;
 use crate::sqs_notification_service::SqsNotificationService;
-use cron::Schedule;
 use sqlx::types::chrono::Utc;
+use sqlx::types::Json;
+use std::collections::HashMap;
 use std::future::Future;
-use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing::instrument;
-use tracing::log::{error, info};
+use tracing::log::{error, info, warn};
+
+const MAX_MESSAGE_ATTRIBUTES: usize = 10;
 
 pub struct OutboxProcessor {
     resources: OutboxProcessorResources,
@@ -118,18 +127,88 @@ impl OutboxProcessor {
         Ok(())
     }
 
+    pub async fn init_singleton_jobs(self) -> Result<(), OutboxPatternProcessorError> {
+        info!("Starting outbox singleton jobs...");
+
+        let registry = self
+            .resources
+            .singleton_jobs
+            .clone()
+            .ok_or_else(|| OutboxPatternProcessorError::new("Missing singleton jobs registry", "Failed to start outbox singleton jobs"))?;
+        let execution_interval = Duration::from_secs((registry.lease_duration_in_seconds / 3).max(1));
+
+        if let Some(box_signal) = self.signal {
+            let mut shutdown_signal = Box::into_pin(box_signal);
+
+            loop {
+                tokio::select! {
+                    result = OutboxProcessor::one_shot_singleton_jobs(&self.resources) => {
+                        if let Err(error) = result {
+                            error!("Outbox singleton jobs failed with error: {}", error.to_string());
+                        }
+                        tokio::time::sleep(execution_interval).await;
+                    }
+                    _ = &mut shutdown_signal => {
+                        break;
+                    }
+                }
+            }
+
+            Self::release_singleton_jobs(&self.resources).await;
+        } else {
+            loop {
+                if let Err(error) = OutboxProcessor::one_shot_singleton_jobs(&self.resources).await {
+                    error!("Outbox singleton jobs failed with error: {}", error.to_string());
+                }
+                tokio::time::sleep(execution_interval).await;
+            }
+        }
+
+        info!("Outbox singleton jobs stopped!");
+
+        Ok(())
+    }
+
+    fn http_gateway(resources: &OutboxProcessorResources) -> Result<HttpGateway, OutboxPatternProcessorError> {
+        if let Some(http_gateway) = resources.http_gateway.get() {
+            return Ok(http_gateway.clone());
+        }
+
+        let http_gateway = HttpGateway::new(resources.http_timeout_in_millis.unwrap_or(3000))?.with_http_auth_profiles(&resources.http_auth_profiles)?;
+
+        Ok(resources.http_gateway.get_or_init(|| http_gateway).clone())
+    }
+
     fn create_app_state(resources: &OutboxProcessorResources) -> Result<AppState, OutboxPatternProcessorError> {
         Ok(AppState {
-            postgres_pool: resources.postgres_pool.clone(),
+            storage: resources.storage.backend(),
+            postgres_pool: resources.postgres_pool().cloned(),
             sqs_client: resources.sqs_client.clone(),
             sns_client: resources.sns_client.clone(),
-            http_gateway: HttpGateway::new(resources.http_timeout_in_millis.unwrap_or(3000))?,
+            http_gateway: Self::http_gateway(resources)?,
             outbox_query_limit: resources.outbox_query_limit,
             delete_after_process_successfully: resources.delete_after_process_successfully,
             max_in_flight_interval_in_seconds: resources.max_in_flight_interval_in_seconds,
+            lease_renewal_interval_in_seconds: resources.lease_renewal_interval_in_seconds,
             outbox_failure_limit: resources.outbox_failure_limit,
             scheduled_clear_locked_partition: resources.scheduled_clear_locked_partition,
             delay_for_failure_attempt_in_seconds: resources.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: resources.http_auth_profiles.clone(),
+            oauth2_token_cache: resources.oauth2_token_cache.clone(),
+            secret_resolvers: resources.secret_resolvers.clone(),
+            destination_registry: resources.destination_registry.clone(),
+            routing_table: resources.routing_table.clone(),
+            cloud_events_mode: resources.cloud_events_mode,
+            payload_encryptor: resources.payload_encryptor.clone(),
+            schema_validator: resources.schema_validator.clone(),
+            lock_strategy: resources.lock_strategy,
+            worker_sharding: resources.worker_sharding,
+            priority_fair_share_percentage: resources.priority_fair_share_percentage,
+            tenant_fairness: resources.tenant_fairness.clone(),
+            processing_mode: resources.processing_mode,
+            unordered_query_limit: resources.unordered_query_limit,
+            unordered_http_concurrency: resources.unordered_http_concurrency,
+            expired_outbox_action: resources.expired_outbox_action,
         })
     }
 
@@ -137,38 +216,163 @@ impl OutboxProcessor {
     pub async fn one_shot_processed_locked_cleaner(resources: &OutboxProcessorResources) -> Result<(), OutboxPatternProcessorError> {
         let app_state = Self::create_app_state(resources)?;
 
-        let mut transaction = app_state.begin_transaction().await?;
+        app_state.storage.clear_processed_locked_partition_key().await
+    }
 
-        if let Some(outbox_clear_schedule) = OutboxRepository::find_cleaner_schedule(&mut transaction).await? {
-            if let Ok(schedule) = Schedule::from_str(&outbox_clear_schedule.cron_expression) {
-                if let Some(next_execution) = schedule.after(&outbox_clear_schedule.last_execution).next() {
-                    let seconds_until_next_execution = (next_execution - Utc::now()).num_seconds();
-                    if seconds_until_next_execution <= 0 {
-                        OutboxRepository::clear_processed_locked_partition_key(&mut transaction).await?;
-                        OutboxRepository::update_last_cleaner_execution(&mut transaction).await?;
-                        app_state.commit_transaction(transaction).await?;
-                    }
+    #[instrument(skip_all, name = "outbox-pattern-processor-singleton-jobs")]
+    pub async fn one_shot_singleton_jobs(resources: &OutboxProcessorResources) -> Result<usize, OutboxPatternProcessorError> {
+        let registry = match &resources.singleton_jobs {
+            None => return Ok(0),
+            Some(registry) => registry,
+        };
+
+        let app_state = Self::create_app_state(resources)?;
+        let leader_election = LeaderElection::from_backend(app_state.storage.clone(), registry.holder_id).with_lease_duration_in_seconds(registry.lease_duration_in_seconds);
+
+        let mut executed = 0;
+        for job in &registry.jobs {
+            let acquired_at = Instant::now();
+            if !leader_election.try_acquire(job.name()).await? || !job.is_due(app_state.storage.find_leadership_last_run_at(job.name()).await?) {
+                continue;
+            }
+
+            let last_run_at = Utc::now();
+
+            match Self::run_singleton_job(&app_state, &leader_election, job.name(), &job.kind, acquired_at).await {
+                Ok(result) => info!("Outbox singleton job {} executed: {}", job.name(), result),
+                Err(error) => error!("Outbox singleton job {} failed with error: {}", job.name(), error.to_string()),
+            }
+
+            app_state.storage.record_leadership_run(job.name(), registry.holder_id, last_run_at).await?;
+            executed += 1;
+        }
+
+        if registry.load_from_database {
+            for job in app_state.storage.find_jobs().await? {
+                let acquired_at = Instant::now();
+                if !job.enabled || !leader_election.try_acquire(&job.name).await? || !job.is_due() {
+                    continue;
                 }
+
+                let last_run_at = Utc::now();
+                let started_at = Instant::now();
+
+                let last_result = match Self::run_singleton_job(&app_state, &leader_election, &job.name, &job.kind, acquired_at).await {
+                    Ok(result) => {
+                        info!("Outbox job {} executed: {}", job.name, result);
+                        result
+                    },
+                    Err(error) => {
+                        error!("Outbox job {} failed with error: {}", job.name, error.to_string());
+                        format!("error: {}", error)
+                    },
+                };
+
+                app_state
+                    .storage
+                    .record_job_run(&job.name, last_run_at, &last_result, started_at.elapsed().as_millis() as i64)
+                    .await?;
+                executed += 1;
             }
         }
 
-        Ok(())
+        Ok(executed)
+    }
+
+    async fn run_singleton_job(
+        app_state: &AppState,
+        leader_election: &LeaderElection,
+        name: &str,
+        kind: &SingletonJobKind,
+        acquired_at: Instant,
+    ) -> Result<String, OutboxPatternProcessorError> {
+        tokio::select! {
+            result = kind.run(app_state) => result,
+            error = Self::renew_leadership_while_running(leader_election, name, acquired_at) => Err(error),
+        }
+    }
+
+    async fn renew_leadership_while_running(
+        leader_election: &LeaderElection,
+        name: &str,
+        acquired_at: Instant,
+    ) -> OutboxPatternProcessorError {
+        let lease_duration = Duration::from_secs(leader_election.lease_duration_in_seconds);
+        let renewal_interval = Duration::from_secs((leader_election.lease_duration_in_seconds / 3).max(1));
+
+        let mut lease_expires_at = acquired_at + lease_duration;
+
+        loop {
+            tokio::time::sleep(renewal_interval.min(lease_expires_at.saturating_duration_since(Instant::now()))).await;
+
+            let renewed_at = Instant::now();
+            match leader_election.try_acquire(name).await {
+                Ok(true) => lease_expires_at = renewed_at + lease_duration,
+                Ok(false) => {
+                    error!("Outbox singleton job {} leadership lost, aborting it", name);
+                    return OutboxPatternProcessorError::new("Leadership lost", &format!("Failed to renew outbox singleton job {name} leadership"));
+                },
+                Err(error) if Instant::now() >= lease_expires_at => {
+                    error!("Outbox singleton job {} leadership expired after failing to renew it, aborting it", name);
+                    return error;
+                },
+                Err(error) => error!("Failed to renew outbox singleton job {} leadership, retrying: {}", name, error.cause),
+            }
+        }
+    }
+
+    async fn release_singleton_jobs(resources: &OutboxProcessorResources) {
+        if let Some(registry) = &resources.singleton_jobs {
+            let leader_election = LeaderElection::new(resources.storage.clone(), registry.holder_id);
+
+            let mut names = registry.jobs.iter().map(|it| it.name().to_string()).collect::<Vec<String>>();
+            if registry.load_from_database {
+                match OutboxJobRepository::new(resources.storage.clone()).find_all().await {
+                    Ok(jobs) => names.extend(jobs.into_iter().map(|it| it.name)),
+                    Err(error) => error!("Failed to list outbox jobs with error: {}", error.to_string()),
+                }
+            }
+
+            for name in names {
+                if let Err(error) = leader_election.release(&name).await {
+                    error!("Failed to release outbox singleton job {} with error: {}", name, error.to_string());
+                }
+            }
+        }
     }
 
     #[instrument(skip_all, name = "outbox-pattern-processor")]
     pub async fn one_shot_process(resources: &OutboxProcessorResources) -> Result<usize, OutboxPatternProcessorError> {
         let app_state = Self::create_app_state(resources)?;
 
-        let outboxes = OutboxRepository::list(&app_state).await?;
+        let claimed_at = Instant::now();
+        let outboxes = app_state.storage.list(&app_state).await?;
         let outboxes_len = outboxes.len();
 
-        let grouped_outboxes = Self::group_by_destination(outboxes.clone());
+        let (live_outboxes, expired_outboxes) = Self::split_expired(&app_state, outboxes.clone());
+
+        let (decoded_outboxes, undecodable_outboxes) = Self::decode_payloads(&app_state, live_outboxes).await;
+
+        let (valid_outboxes, mut invalid_outboxes, unvalidated_outboxes) = Self::validate_payloads(&app_state, decoded_outboxes).await;
+
+        invalid_outboxes.extend(expired_outboxes);
 
-        let sqs_notification_result = SqsNotificationService::send(&app_state, &grouped_outboxes).await?;
-        let sns_notification_result = SnsNotificationService::send(&app_state, &grouped_outboxes).await?;
-        let http_notification_result = HttpNotificationService::send(&app_state, &grouped_outboxes).await?;
+        let (routed_outboxes, unroutable_outboxes) = Self::route_by_content(&app_state, valid_outboxes);
+
+        let registered_destinations = Self::registered_destinations(&app_state, &routed_outboxes).await?;
+
+        let grouped_outboxes = Self::group_by_destination(&app_state, routed_outboxes, &registered_destinations);
+
+        let (sqs_notification_result, sns_notification_result, http_notification_result) = tokio::select! {
+            notification_results = Self::dispatch(&app_state, &grouped_outboxes) => notification_results?,
+            error = Self::renew_lease_while_dispatching(&app_state, claimed_at) => return Err(error),
+        };
 
         let mut failure_outbox = vec![];
+        failure_outbox.extend(undecodable_outboxes);
+        failure_outbox.extend(unvalidated_outboxes);
+        failure_outbox.extend(unroutable_outboxes);
+        failure_outbox.extend(grouped_outboxes.failed.clone());
         failure_outbox.extend(sqs_notification_result.failed);
         failure_outbox.extend(sns_notification_result.failed);
         failure_outbox.extend(http_notification_result.failed);
@@ -176,45 +380,341 @@ impl OutboxProcessor {
         let successfully_outboxes = outboxes
             .into_iter()
             .filter(|it| !failure_outbox.iter().any(|failed| failed.idempotent_key == it.idempotent_key))
+            .filter(|it| !invalid_outboxes.iter().any(|invalid| invalid.idempotent_key == it.idempotent_key))
             .collect::<Vec<Outbox>>();
 
-        let mut transaction = app_state.begin_transaction().await?;
+        app_state.storage.complete(&app_state, &successfully_outboxes, &failure_outbox, &invalid_outboxes).await?;
 
-        if app_state.delete_after_process_successfully.unwrap_or(false) {
-            OutboxRepository::delete_processed(&app_state, &mut transaction, &successfully_outboxes).await?;
+        Ok(outboxes_len)
+    }
+
+    #[instrument(skip_all)]
+    async fn dispatch(
+        app_state: &AppState,
+        grouped_outboxes: &GroupedOutboxed,
+    ) -> Result<(NotificationResult, NotificationResult, NotificationResult), OutboxPatternProcessorError> {
+        let sqs_notification_result = SqsNotificationService::send(app_state, grouped_outboxes).await?;
+        let sns_notification_result = SnsNotificationService::send(app_state, grouped_outboxes).await?;
+        let http_notification_result = HttpNotificationService::send(app_state, grouped_outboxes).await?;
+
+        Ok((sqs_notification_result, sns_notification_result, http_notification_result))
+    }
+
+    async fn renew_lease_while_dispatching(
+        app_state: &AppState,
+        claimed_at: Instant,
+    ) -> OutboxPatternProcessorError {
+        let lease_duration = Duration::from_secs(app_state.max_in_flight_interval_in_seconds.unwrap_or(30));
+        let renewal_interval = Duration::from_secs(
+            app_state
+                .lease_renewal_interval_in_seconds
+                .unwrap_or(app_state.max_in_flight_interval_in_seconds.unwrap_or(30) / 3)
+                .max(1),
+        );
+
+        let mut lease_expires_at = claimed_at + lease_duration;
+
+        loop {
+            tokio::time::sleep(renewal_interval.min(lease_expires_at.saturating_duration_since(Instant::now()))).await;
+
+            let renewed_at = Instant::now();
+            match app_state.storage.renew_lease(app_state).await {
+                Ok(true) => lease_expires_at = renewed_at + lease_duration,
+                Ok(false) => {
+                    error!("Outbox processor lease lost, aborting dispatch");
+                    return OutboxPatternProcessorError::new("Lease lost", "Failed to renew partition keys lease");
+                },
+                Err(error) if Instant::now() >= lease_expires_at => {
+                    error!("Outbox processor lease expired after failing to renew it, aborting dispatch");
+                    return error;
+                },
+                Err(error) => error!("Failed to renew outbox processor lease, retrying: {}", error.cause),
+            }
+        }
+    }
+
+    #[instrument(skip_all)]
+    fn split_expired(
+        app_state: &AppState,
+        outboxes: Vec<Outbox>,
+    ) -> (Vec<Outbox>, Vec<Outbox>) {
+        let (expired_outboxes, live_outboxes): (Vec<_>, Vec<_>) = outboxes.into_iter().partition(|outbox| outbox.is_expired());
+
+        match app_state.expired_outbox_action.unwrap_or_default() {
+            ExpiredOutboxAction::DeadLetter => {
+                for outbox in &expired_outboxes {
+                    error!("Dead lettering expired outbox for idempotent_key {}", outbox.idempotent_key);
+                }
+                (live_outboxes, expired_outboxes)
+            },
+            ExpiredOutboxAction::Discard => {
+                for outbox in &expired_outboxes {
+                    info!("Discarding expired outbox for idempotent_key {}", outbox.idempotent_key);
+                }
+                (live_outboxes, vec![])
+            },
+        }
+    }
+
+    #[instrument(skip_all)]
+    async fn decode_payloads(
+        app_state: &AppState,
+        outboxes: Vec<Outbox>,
+    ) -> (Vec<Outbox>, Vec<Outbox>) {
+        let mut decoded_outboxes = vec![];
+        let mut undecodable_outboxes = vec![];
+
+        for outbox in outboxes {
+            let decrypted = match (&app_state.payload_encryptor, &outbox.encryption) {
+                (Some(encryptor), Some(_)) => encryptor.decrypt(&outbox).await,
+                (None, Some(_)) => Err(OutboxPatternProcessorError::new("Missing payload encryptor", "Failed to decrypt payload")),
+                _ => Ok(outbox.clone()),
+            };
+
+            match decrypted
+                .and_then(|decrypted_outbox| decrypted_outbox.decompress())
+                .map(|decompressed_outbox| decompressed_outbox.expand_json_payload())
+            {
+                Ok(decoded_outbox) => decoded_outboxes.push(decoded_outbox),
+                Err(error) => {
+                    error!(
+                        "{} for idempotent_key {} - Cause: {}",
+                        error.message.unwrap_or("Failed to decode payload".to_string()),
+                        outbox.idempotent_key,
+                        error.cause
+                    );
+                    undecodable_outboxes.push(outbox);
+                },
+            }
+        }
+
+        (decoded_outboxes, undecodable_outboxes)
+    }
+
+    #[instrument(skip_all)]
+    async fn validate_payloads(
+        app_state: &AppState,
+        outboxes: Vec<Outbox>,
+    ) -> (Vec<Outbox>, Vec<Outbox>, Vec<Outbox>) {
+        let validator = if let Some(validator) = &app_state.schema_validator {
+            validator
         } else {
-            OutboxRepository::mark_as_processed(&app_state, &mut transaction, &successfully_outboxes).await?;
+            return (outboxes, vec![], vec![]);
+        };
+
+        let mut valid_outboxes = vec![];
+        let mut invalid_outboxes = vec![];
+        let mut unvalidated_outboxes = vec![];
+
+        for outbox in outboxes {
+            match validator.validate(&outbox).await {
+                Ok(violations) if violations.is_empty() => valid_outboxes.push(outbox),
+                Ok(violations) => {
+                    error!(
+                        "Dead lettering invalid payload for idempotent_key {} - Cause: {}",
+                        outbox.idempotent_key,
+                        violations.join("; ")
+                    );
+                    invalid_outboxes.push(outbox);
+                },
+                Err(error) => {
+                    error!(
+                        "{} for idempotent_key {} - Cause: {}",
+                        error.message.unwrap_or("Failed to validate payload".to_string()),
+                        outbox.idempotent_key,
+                        error.cause
+                    );
+                    unvalidated_outboxes.push(outbox);
+                },
+            }
         }
 
-        if !failure_outbox.is_empty() {
-            OutboxRepository::increase_attempts(&app_state, &mut transaction, &failure_outbox).await?;
+        (valid_outboxes, invalid_outboxes, unvalidated_outboxes)
+    }
+
+    #[instrument(skip_all)]
+    fn route_by_content(
+        app_state: &AppState,
+        outboxes: Vec<Outbox>,
+    ) -> (Vec<Outbox>, Vec<Outbox>) {
+        if app_state.routing_table.rules.is_empty() {
+            return (outboxes, vec![]);
         }
 
-        app_state.commit_transaction(transaction).await?;
+        let mut routed_outboxes = vec![];
+        let mut unroutable_outboxes = vec![];
 
-        Ok(outboxes_len)
+        for outbox in outboxes {
+            if !outbox.destinations.0.is_empty() {
+                routed_outboxes.push(outbox);
+                continue;
+            }
+
+            let destinations = app_state.routing_table.route(&outbox);
+            if destinations.is_empty() {
+                error!("No routing rule matches outbox idempotent_key {}", outbox.idempotent_key);
+                unroutable_outboxes.push(outbox);
+            } else {
+                routed_outboxes.push(Outbox {
+                    destinations: Json(destinations),
+                    ..outbox
+                });
+            }
+        }
+
+        (routed_outboxes, unroutable_outboxes)
+    }
+
+    #[instrument(skip_all)]
+    async fn registered_destinations(
+        app_state: &AppState,
+        outboxes: &[Outbox],
+    ) -> Result<HashMap<String, OutboxDestination>, OutboxPatternProcessorError> {
+        let mut registered_destinations = app_state.destination_registry.destinations.clone();
+
+        let has_named_destination = outboxes.iter().any(|outbox| {
+            outbox
+                .destinations
+                .0
+                .iter()
+                .any(|destination| matches!(destination, OutboxDestination::NamedDestination(_)))
+        });
+
+        if !has_named_destination {
+            return Ok(registered_destinations);
+        }
+
+        if app_state.destination_registry.load_from_database {
+            for registered_destination in app_state.storage.find_registered_destinations().await? {
+                registered_destinations.insert(registered_destination.name, registered_destination.destination.0);
+            }
+        }
+
+        for destination in registered_destinations.values_mut() {
+            if let OutboxDestination::HttpDestination(http) = destination {
+                if let Some(headers) = http.headers.as_mut() {
+                    for value in headers.values_mut() {
+                        *value = app_state.secret_resolvers.resolve_placeholders(value).await;
+                    }
+                }
+            }
+        }
+
+        Ok(registered_destinations)
     }
 
     #[instrument(skip_all)]
-    fn group_by_destination(outboxes: Vec<Outbox>) -> GroupedOutboxed {
+    fn group_by_destination(
+        app_state: &AppState,
+        outboxes: Vec<Outbox>,
+        registered_destinations: &HashMap<String, OutboxDestination>,
+    ) -> GroupedOutboxed {
         let mut grouped_outboxes = GroupedOutboxed::default();
 
         for outbox in outboxes {
-            for destination in outbox.destinations.0.clone() {
-                match destination {
-                    OutboxDestination::SqsDestination(sqs) => {
-                        grouped_outboxes.sqs.entry(sqs.queue_url).or_insert(vec![]).push(outbox.clone());
-                    },
-                    OutboxDestination::SnsDestination(sns) => {
-                        grouped_outboxes.sns.entry(sns.topic_arn).or_insert(vec![]).push(outbox.clone());
-                    },
-                    OutboxDestination::HttpDestination(_) => {
-                        grouped_outboxes.http.push(outbox.clone());
+            match Self::prepare_destinations(app_state, &outbox, registered_destinations) {
+                Ok(prepared_destinations) => {
+                    for (destination, prepared_outbox) in prepared_destinations {
+                        match destination {
+                            OutboxDestination::SqsDestination(sqs) => {
+                                grouped_outboxes.sqs.entry(sqs.queue_url).or_insert(vec![]).push(prepared_outbox);
+                            },
+                            OutboxDestination::SnsDestination(sns) => {
+                                grouped_outboxes.sns.entry(sns.topic_arn).or_insert(vec![]).push(prepared_outbox);
+                            },
+                            OutboxDestination::HttpDestination(http) => {
+                                grouped_outboxes.http.push((http, prepared_outbox));
+                            },
+                            OutboxDestination::NamedDestination(_) => {},
+                        }
+                    }
+                },
+                Err(error) => {
+                    error!(
+                        "{} for idempotent_key {} - Cause: {}",
+                        error.message.unwrap_or("Failed to prepare destinations".to_string()),
+                        outbox.idempotent_key,
+                        error.cause
+                    );
+                    grouped_outboxes.failed.push(outbox);
+                },
+            }
+        }
+
+        grouped_outboxes
+    }
+
+    fn prepare_destinations(
+        app_state: &AppState,
+        outbox: &Outbox,
+        registered_destinations: &HashMap<String, OutboxDestination>,
+    ) -> Result<Vec<(OutboxDestination, Outbox)>, OutboxPatternProcessorError> {
+        let mut prepared_destinations = vec![];
+
+        for destination in outbox.destinations.0.clone() {
+            let resolved_destination = if let OutboxDestination::NamedDestination(named) = destination {
+                match registered_destinations.get(&named.name) {
+                    Some(OutboxDestination::NamedDestination(_)) | None => {
+                        return Err(OutboxPatternProcessorError::new(
+                            &format!("Unknown destination name {}", named.name),
+                            "Failed to resolve destination name",
+                        ));
                     },
+                    Some(registered_destination) => registered_destination.clone(),
+                }
+            } else if let OutboxDestination::HttpDestination(mut http) = destination {
+                if let Some(headers) = http.headers.as_mut() {
+                    for value in headers.values_mut() {
+                        *value = app_state.secret_resolvers.resolve_environment_placeholders(value);
+                    }
                 }
+                OutboxDestination::HttpDestination(http)
+            } else {
+                destination
+            };
+
+            let (transforms, cloud_events_mode, compression) = match &resolved_destination {
+                OutboxDestination::SqsDestination(sqs) => (&sqs.transforms, sqs.cloud_events, sqs.compression),
+                OutboxDestination::SnsDestination(sns) => (&sns.transforms, sns.cloud_events, sns.compression),
+                OutboxDestination::HttpDestination(http) => (&http.transforms, http.cloud_events, http.compression),
+                OutboxDestination::NamedDestination(_) => (&None, None, None),
+            };
+
+            let transformed_outbox = PayloadTransform::apply_all(transforms, outbox)?;
+            let cloud_events_mode = cloud_events_mode.or(app_state.cloud_events_mode);
+
+            let mut prepared_outbox = Self::wrap(&transformed_outbox, cloud_events_mode, compression)?;
+
+            let is_message_destination = matches!(resolved_destination, OutboxDestination::SqsDestination(_) | OutboxDestination::SnsDestination(_));
+            if is_message_destination && cloud_events_mode == Some(CloudEventsMode::Binary) && prepared_outbox.message_attributes().len() + 1 > MAX_MESSAGE_ATTRIBUTES {
+                warn!(
+                    "Outbox idempotent_key {} exceeds {} message attributes as binary cloud event, sending it as structured cloud event",
+                    outbox.idempotent_key, MAX_MESSAGE_ATTRIBUTES
+                );
+                prepared_outbox = Self::wrap(&transformed_outbox, Some(CloudEventsMode::Structured), compression)?;
             }
+
+            prepared_destinations.push((resolved_destination, prepared_outbox));
         }
 
-        grouped_outboxes
+        Ok(prepared_destinations)
+    }
+
+    fn wrap(
+        outbox: &Outbox,
+        cloud_events_mode: Option<CloudEventsMode>,
+        compression: Option<PayloadCompression>,
+    ) -> Result<Outbox, OutboxPatternProcessorError> {
+        let mut wrapped_outbox = outbox.clone();
+
+        if let Some(mode) = cloud_events_mode {
+            wrapped_outbox = mode.wrap(&wrapped_outbox)?;
+        }
+
+        if let Some(compression) = compression {
+            wrapped_outbox = compression.wrap(&wrapped_outbox)?;
+        }
+
+        Ok(wrapped_outbox)
     }
 }
@@ -1,55 +1,359 @@
 use crate::error::OutboxPatternProcessorError;
 use crate::outbox::Outbox;
-use sqlx::PgConnection;
+use crate::outbox_columns::{OutboxColumns, OUTBOX_INSERT_MAX_ROWS};
+use crate::outbox_copy::OutboxCopyEncoder;
+use crate::outbox_event::OutboxEvent;
+use crate::outbox_insert_options::OutboxInsertOptions;
+use crate::outbox_insert_result::OutboxInsertResult;
+use crate::schema_validator::SchemaValidator;
+use sqlx::{Acquire, PgConnection, Postgres, QueryBuilder, Row};
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
 use tracing::instrument;
+use uuid::Uuid;
+
+const BULK_INSERT_CHUNK_SIZE: usize = 10_000;
+
+type ConnectionFuture<'x, T> = Pin<Box<dyn Future<Output = Result<T, OutboxPatternProcessorError>> + Send + 'x>>;
 
 pub struct OutboxRepository;
 
 impl OutboxRepository {
     #[instrument(skip_all)]
-    pub async fn insert(
-        db_conn: &mut PgConnection,
+    pub async fn insert<'c, A>(
+        db_conn: A,
         outbox: Outbox,
-    ) -> Result<Outbox, OutboxPatternProcessorError> {
+    ) -> Result<Outbox, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
         let outboxes = Self::insert_all(db_conn, vec![outbox]).await?;
         Ok(outboxes[0].clone())
     }
 
-    pub async fn insert_all(
-        db_conn: &mut PgConnection,
+    #[instrument(skip_all)]
+    pub async fn insert_event<'c, A, E>(
+        db_conn: A,
+        event: &E,
+    ) -> Result<Outbox, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+        E: OutboxEvent,
+    {
+        let outbox = Outbox::event(event)?;
+
+        Self::insert(db_conn, outbox).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn insert_all_validated<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+        validator: &'c SchemaValidator,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
+        Self::insert_all_with_options(db_conn, outboxes, &OutboxInsertOptions::new().with_validator(validator.clone())).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn insert_all<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
+        if outboxes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Self::with_connection(db_conn, "Failed to acquire connection to insert outboxes", move |conn| {
+            Box::pin(Self::insert_all_with_connection(conn, outboxes))
+        })
+        .await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn insert_all_with_options<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+        options: &OutboxInsertOptions,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
+        let outboxes = options.prepare(outboxes).await?;
+
+        Self::insert_all(db_conn, outboxes).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn insert_all_ignoring_conflicts<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+    ) -> Result<OutboxInsertResult, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
+        if outboxes.is_empty() {
+            return Ok(OutboxInsertResult::default());
+        }
+
+        Self::with_connection(db_conn, "Failed to acquire connection to insert outboxes", move |conn| {
+            Box::pin(Self::insert_all_ignoring_conflicts_with_connection(conn, outboxes))
+        })
+        .await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn insert_all_ignoring_conflicts_with_options<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+        options: &OutboxInsertOptions,
+    ) -> Result<OutboxInsertResult, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
+        let outboxes = options.prepare(outboxes).await?;
+
+        Self::insert_all_ignoring_conflicts(db_conn, outboxes).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn cancel<'c, A>(
+        db_conn: A,
+        idempotent_key: Uuid,
+    ) -> Result<bool, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
+        Self::with_connection(db_conn, "Failed to acquire connection to cancel outbox", move |conn| {
+            Box::pin(async move {
+                let sql = r#"delete from outbox
+where idempotent_key in (
+    select o.idempotent_key
+    from outbox o
+    where o.idempotent_key = $1
+        and o.processed_at is null
+        and (o.unordered or not exists (
+            select 1
+            from outbox_lock l
+            where l.partition_key = o.partition_key
+                and l.processed_at is null
+                and l.processing_until > now()
+        ))
+    for update of o skip locked
+)"#;
+
+                let result = sqlx::query(sql)
+                    .bind(idempotent_key)
+                    .execute(&mut *conn)
+                    .await
+                    .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to cancel outbox idempotent_key={idempotent_key}")))?;
+
+                Ok(result.rows_affected() > 0)
+            })
+        })
+        .await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn bulk_insert<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+    ) -> Result<u64, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
+        Self::bulk_insert_with_chunk_size(db_conn, outboxes, BULK_INSERT_CHUNK_SIZE).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn bulk_insert_with_chunk_size<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+        chunk_size: usize,
+    ) -> Result<u64, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+    {
+        if outboxes.is_empty() {
+            return Ok(0);
+        }
+
+        Self::with_connection(db_conn, "Failed to acquire connection to insert outboxes", move |conn| {
+            Box::pin(async move {
+                let mut inserted = 0;
+                for chunk in outboxes.chunks(chunk_size.max(1)) {
+                    let data = OutboxCopyEncoder::encode(chunk)?;
+
+                    let mut copy_in = conn
+                        .copy_in_raw(&OutboxCopyEncoder::statement())
+                        .await
+                        .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to start outboxes copy"))?;
+
+                    if let Err(error) = copy_in.send(data).await {
+                        let _ = copy_in.abort(error.to_string()).await;
+                        return Err(OutboxPatternProcessorError::new(&error.to_string(), &Self::insert_error_message(chunk)));
+                    }
+
+                    inserted += copy_in
+                        .finish()
+                        .await
+                        .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &Self::insert_error_message(chunk)))?;
+                }
+
+                Ok(inserted)
+            })
+        })
+        .await
+    }
+
+    fn with_connection<'c, A, T, F>(
+        db_conn: A,
+        acquire_error_message: &'static str,
+        run: F,
+    ) -> ConnectionFuture<'c, T>
+    where
+        A: Acquire<'c, Database = Postgres> + Send + 'c,
+        F: for<'x> FnOnce(&'x mut PgConnection) -> ConnectionFuture<'x, T> + Send + 'c,
+    {
+        Box::pin(async move {
+            let mut conn = db_conn
+                .acquire()
+                .await
+                .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), acquire_error_message))?;
+
+            run(&mut conn).await
+        })
+    }
+
+    #[instrument(skip_all)]
+    async fn insert_all_with_connection(
+        conn: &mut PgConnection,
         outboxes: Vec<Outbox>,
     ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
         if outboxes.is_empty() {
             return Ok(vec![]);
         }
 
-        let sql = r#"
-            INSERT INTO outbox
-                (idempotent_key, partition_key, destinations, headers, payload, created_at, process_after)
-        "#;
-
-        let mut query_builder = sqlx::QueryBuilder::new(sql);
-
-        query_builder.push_values(outboxes.clone(), |mut b, outbox: Outbox| {
-            b.push_bind(outbox.idempotent_key)
-                .push_bind(outbox.partition_key)
-                .push_bind(outbox.destinations)
-                .push_bind(outbox.headers)
-                .push_bind(outbox.payload)
-                .push_bind(outbox.created_at)
-                .push_bind(outbox.process_after.unwrap_or(outbox.created_at));
-        });
+        if outboxes.len() <= OUTBOX_INSERT_MAX_ROWS {
+            Self::insert_chunk(conn, &outboxes).await?;
+            return Ok(outboxes);
+        }
 
-        query_builder.build().execute(&mut *db_conn).await.map_err(|error| {
-            OutboxPatternProcessorError::new(
-                &error.to_string(),
-                &format!(
-                    "Failed to insert outboxes to partition_keys={}",
-                    outboxes.iter().map(|it| it.partition_key.to_string()).collect::<Vec<_>>().join(", ")
-                ),
-            )
-        })?;
+        let mut transaction = conn
+            .begin()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to begin transaction to insert outboxes"))?;
+
+        for chunk in outboxes.chunks(OUTBOX_INSERT_MAX_ROWS) {
+            Self::insert_chunk(&mut transaction, chunk).await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to commit transaction to insert outboxes"))?;
 
         Ok(outboxes)
     }
+
+    #[instrument(skip_all)]
+    async fn insert_all_ignoring_conflicts_with_connection(
+        conn: &mut PgConnection,
+        outboxes: Vec<Outbox>,
+    ) -> Result<OutboxInsertResult, OutboxPatternProcessorError> {
+        let inserted_keys = if outboxes.len() <= OUTBOX_INSERT_MAX_ROWS {
+            Self::insert_chunk_ignoring_conflicts(conn, &outboxes).await?
+        } else {
+            let mut transaction = conn
+                .begin()
+                .await
+                .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to begin transaction to insert outboxes"))?;
+
+            let mut inserted_keys = HashSet::new();
+            for chunk in outboxes.chunks(OUTBOX_INSERT_MAX_ROWS) {
+                inserted_keys.extend(Self::insert_chunk_ignoring_conflicts(&mut transaction, chunk).await?);
+            }
+
+            transaction
+                .commit()
+                .await
+                .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to commit transaction to insert outboxes"))?;
+
+            inserted_keys
+        };
+
+        let (inserted, existing) = outboxes.into_iter().partition(|outbox| inserted_keys.contains(&outbox.idempotent_key));
+
+        Ok(OutboxInsertResult { inserted, existing })
+    }
+
+    async fn insert_chunk(
+        conn: &mut PgConnection,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        Self::insert_query_builder(outboxes)?
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &Self::insert_error_message(outboxes)))?;
+
+        Ok(())
+    }
+
+    async fn insert_chunk_ignoring_conflicts(
+        conn: &mut PgConnection,
+        outboxes: &[Outbox],
+    ) -> Result<HashSet<Uuid>, OutboxPatternProcessorError> {
+        let mut query_builder = Self::insert_query_builder(outboxes)?;
+        query_builder.push(" ON CONFLICT (idempotent_key) DO NOTHING RETURNING idempotent_key");
+
+        Ok(query_builder
+            .build()
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &Self::insert_error_message(outboxes)))?
+            .iter()
+            .map(|row| row.get::<Uuid, _>("idempotent_key"))
+            .collect())
+    }
+
+    fn insert_query_builder(outboxes: &[Outbox]) -> Result<QueryBuilder<'_, Postgres>, OutboxPatternProcessorError> {
+        let rows = outboxes.iter().map(OutboxColumns::from_outbox).collect::<Result<Vec<_>, _>>()?;
+
+        let mut query_builder = QueryBuilder::new(format!("INSERT INTO outbox ({})", OutboxColumns::names()));
+
+        query_builder.push_values(rows, |mut b, row: OutboxColumns| {
+            b.push_bind(row.idempotent_key)
+                .push_bind(row.partition_key)
+                .push_bind(row.destinations)
+                .push_bind(row.headers)
+                .push_bind(row.payload)
+                .push_bind(row.payload_binary)
+                .push_bind(row.content_type)
+                .push_bind(row.content_encoding)
+                .push_bind(row.encryption)
+                .push_bind(row.created_at)
+                .push_bind(row.process_after)
+                .push_bind(row.priority)
+                .push_bind(row.tenant)
+                .push_bind(row.unordered)
+                .push_bind(row.expires_at)
+                .push_bind(row.payload_json);
+        });
+
+        Ok(query_builder)
+    }
+
+    fn insert_error_message(outboxes: &[Outbox]) -> String {
+        format!(
+            "Failed to insert outboxes to partition_keys={}",
+            outboxes.iter().map(|it| it.partition_key.to_string()).collect::<Vec<_>>().join(", ")
+        )
+    }
 }
@@ -1,9 +1,27 @@
 use crate::aws::{SnsClient, SqsClient};
+use crate::cloud_events::CloudEventsMode;
+use crate::destination_registry::DestinationRegistry;
+use crate::expired_outbox_action::ExpiredOutboxAction;
+use crate::http_auth_profile::HttpAuthProfile;
+use crate::http_gateway::HttpGateway;
+use crate::lock_strategy::LockStrategy;
+use crate::oauth2_token_cache::OAuth2TokenCache;
+use crate::outbox_storage::OutboxStorage;
+use crate::payload_encryptor::PayloadEncryptor;
+use crate::processing_mode::ProcessingMode;
+use crate::routing_table::RoutingTable;
+use crate::schema_validator::SchemaValidator;
+use crate::secret_resolvers::SecretResolvers;
+use crate::singleton_job_registry::SingletonJobRegistry;
+use crate::tenant_fairness::TenantFairness;
+use crate::worker_sharding::WorkerSharding;
 use sqlx::{Pool, Postgres};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 #[derive(Clone)]
 pub struct OutboxProcessorResources {
-    pub postgres_pool: Pool<Postgres>,
+    pub storage: OutboxStorage,
     pub sqs_client: Option<SqsClient>,
     pub sns_client: Option<SnsClient>,
     pub http_timeout_in_millis: Option<u64>,
@@ -15,16 +33,35 @@ pub struct OutboxProcessorResources {
     pub scheduled_clear_locked_partition: Option<bool>,
     pub outbox_cleaner_execution_interval_in_seconds: Option<u64>,
     pub delay_for_failure_attempt_in_seconds: Option<u64>,
+    pub http_auth_profiles: HashMap<String, HttpAuthProfile>,
+    pub oauth2_token_cache: OAuth2TokenCache,
+    pub secret_resolvers: SecretResolvers,
+    pub destination_registry: DestinationRegistry,
+    pub routing_table: RoutingTable,
+    pub cloud_events_mode: Option<CloudEventsMode>,
+    pub payload_encryptor: Option<PayloadEncryptor>,
+    pub schema_validator: Option<SchemaValidator>,
+    pub lock_strategy: Option<LockStrategy>,
+    pub lease_renewal_interval_in_seconds: Option<u64>,
+    pub worker_sharding: Option<WorkerSharding>,
+    pub singleton_jobs: Option<SingletonJobRegistry>,
+    pub priority_fair_share_percentage: Option<u32>,
+    pub tenant_fairness: Option<TenantFairness>,
+    pub processing_mode: Option<ProcessingMode>,
+    pub unordered_query_limit: Option<u32>,
+    pub unordered_http_concurrency: Option<u32>,
+    pub expired_outbox_action: Option<ExpiredOutboxAction>,
+    pub(crate) http_gateway: Arc<OnceLock<HttpGateway>>,
 }
 
 impl OutboxProcessorResources {
     pub fn new(
-        postgres_pool: Pool<Postgres>,
+        storage: impl Into<OutboxStorage>,
         sqs_client: Option<SqsClient>,
         sns_client: Option<SnsClient>,
     ) -> Self {
         Self {
-            postgres_pool,
+            storage: storage.into(),
             sqs_client,
             sns_client,
             http_timeout_in_millis: None,
@@ -36,15 +73,38 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: None,
             outbox_cleaner_execution_interval_in_seconds: None,
             delay_for_failure_attempt_in_seconds: None,
+            http_auth_profiles: HashMap::new(),
+            oauth2_token_cache: OAuth2TokenCache::default(),
+            secret_resolvers: SecretResolvers::default(),
+            destination_registry: DestinationRegistry::default(),
+            routing_table: RoutingTable::default(),
+            cloud_events_mode: None,
+            payload_encryptor: None,
+            schema_validator: None,
+            lock_strategy: None,
+            lease_renewal_interval_in_seconds: None,
+            worker_sharding: None,
+            singleton_jobs: None,
+            priority_fair_share_percentage: None,
+            tenant_fairness: None,
+            processing_mode: None,
+            unordered_query_limit: None,
+            unordered_http_concurrency: None,
+            expired_outbox_action: None,
+            http_gateway: Arc::new(OnceLock::new()),
         }
     }
 
+    pub fn postgres_pool(&self) -> Option<&Pool<Postgres>> {
+        self.storage.postgres_pool()
+    }
+
     pub fn with_http_timeout_in_millis(
         self,
         http_timeout: u64,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: Some(http_timeout),
@@ -56,6 +116,25 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
             outbox_cleaner_execution_interval_in_seconds: None,
             delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: Arc::new(OnceLock::new()),
         }
     }
 
@@ -64,7 +143,7 @@ impl OutboxProcessorResources {
         outbox_query_limit: u32,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: self.http_timeout_in_millis,
@@ -76,6 +155,25 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
             outbox_cleaner_execution_interval_in_seconds: None,
             delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
         }
     }
 
@@ -84,7 +182,7 @@ impl OutboxProcessorResources {
         outbox_execution_interval_in_seconds: u64,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: self.http_timeout_in_millis,
@@ -96,6 +194,25 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
             outbox_cleaner_execution_interval_in_seconds: None,
             delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
         }
     }
 
@@ -104,7 +221,7 @@ impl OutboxProcessorResources {
         delete_after_process_successfully: bool,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: self.http_timeout_in_millis,
@@ -116,6 +233,25 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
             outbox_cleaner_execution_interval_in_seconds: None,
             delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
         }
     }
 
@@ -124,7 +260,7 @@ impl OutboxProcessorResources {
         max_in_flight_interval_in_seconds: u64,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: self.http_timeout_in_millis,
@@ -136,6 +272,25 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
             outbox_cleaner_execution_interval_in_seconds: None,
             delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
         }
     }
 
@@ -144,7 +299,7 @@ impl OutboxProcessorResources {
         outbox_failure_limit: u32,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: self.http_timeout_in_millis,
@@ -156,6 +311,25 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
             outbox_cleaner_execution_interval_in_seconds: None,
             delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
         }
     }
 
@@ -164,7 +338,7 @@ impl OutboxProcessorResources {
         scheduled_clear_locked_partition: bool,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: self.http_timeout_in_millis,
@@ -176,6 +350,25 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: Some(scheduled_clear_locked_partition),
             outbox_cleaner_execution_interval_in_seconds: None,
             delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
         }
     }
 
@@ -184,7 +377,7 @@ impl OutboxProcessorResources {
         outbox_cleaner_execution_interval_in_seconds: u64,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: self.http_timeout_in_millis,
@@ -196,6 +389,25 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
             outbox_cleaner_execution_interval_in_seconds: Some(outbox_cleaner_execution_interval_in_seconds),
             delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
         }
     }
 
@@ -204,7 +416,7 @@ impl OutboxProcessorResources {
         delay_for_failure_attempt_in_seconds: u64,
     ) -> Self {
         Self {
-            postgres_pool: self.postgres_pool,
+            storage: self.storage,
             sqs_client: self.sqs_client,
             sns_client: self.sns_client,
             http_timeout_in_millis: self.http_timeout_in_millis,
@@ -216,6 +428,688 @@ impl OutboxProcessorResources {
             scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
             outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
             delay_for_failure_attempt_in_seconds: Some(delay_for_failure_attempt_in_seconds),
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_http_auth_profiles(
+        self,
+        http_auth_profiles: HashMap<String, HttpAuthProfile>,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn with_secret_resolvers(
+        self,
+        secret_resolvers: SecretResolvers,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_destination_registry(
+        self,
+        destination_registry: DestinationRegistry,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_routing_table(
+        self,
+        routing_table: RoutingTable,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_cloud_events_mode(
+        self,
+        cloud_events_mode: CloudEventsMode,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: Some(cloud_events_mode),
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_payload_encryptor(
+        self,
+        payload_encryptor: PayloadEncryptor,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: Some(payload_encryptor),
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_schema_validator(
+        self,
+        schema_validator: SchemaValidator,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: Some(schema_validator),
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_lock_strategy(
+        self,
+        lock_strategy: LockStrategy,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: Some(lock_strategy),
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_lease_renewal_interval_in_seconds(
+        self,
+        lease_renewal_interval_in_seconds: u64,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: Some(lease_renewal_interval_in_seconds),
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_worker_sharding(
+        self,
+        worker_sharding: WorkerSharding,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: Some(worker_sharding),
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_singleton_jobs(
+        self,
+        singleton_jobs: SingletonJobRegistry,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: Some(singleton_jobs),
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_priority_fair_share_percentage(
+        self,
+        priority_fair_share_percentage: u32,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: Some(priority_fair_share_percentage),
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_tenant_fairness(
+        self,
+        tenant_fairness: TenantFairness,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: Some(tenant_fairness),
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_processing_mode(
+        self,
+        processing_mode: ProcessingMode,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: Some(processing_mode),
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_unordered_query_limit(
+        self,
+        unordered_query_limit: u32,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: Some(unordered_query_limit),
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_unordered_http_concurrency(
+        self,
+        unordered_http_concurrency: u32,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: Some(unordered_http_concurrency),
+            expired_outbox_action: self.expired_outbox_action,
+            http_gateway: self.http_gateway,
+        }
+    }
+
+    pub fn with_expired_outbox_action(
+        self,
+        expired_outbox_action: ExpiredOutboxAction,
+    ) -> Self {
+        Self {
+            storage: self.storage,
+            sqs_client: self.sqs_client,
+            sns_client: self.sns_client,
+            http_timeout_in_millis: self.http_timeout_in_millis,
+            outbox_query_limit: self.outbox_query_limit,
+            outbox_execution_interval_in_seconds: self.outbox_execution_interval_in_seconds,
+            delete_after_process_successfully: self.delete_after_process_successfully,
+            max_in_flight_interval_in_seconds: self.max_in_flight_interval_in_seconds,
+            outbox_failure_limit: self.outbox_failure_limit,
+            scheduled_clear_locked_partition: self.scheduled_clear_locked_partition,
+            outbox_cleaner_execution_interval_in_seconds: self.outbox_cleaner_execution_interval_in_seconds,
+            delay_for_failure_attempt_in_seconds: self.delay_for_failure_attempt_in_seconds,
+            http_auth_profiles: self.http_auth_profiles,
+            oauth2_token_cache: self.oauth2_token_cache,
+            secret_resolvers: self.secret_resolvers,
+            destination_registry: self.destination_registry,
+            routing_table: self.routing_table,
+            cloud_events_mode: self.cloud_events_mode,
+            payload_encryptor: self.payload_encryptor,
+            schema_validator: self.schema_validator,
+            lock_strategy: self.lock_strategy,
+            lease_renewal_interval_in_seconds: self.lease_renewal_interval_in_seconds,
+            worker_sharding: self.worker_sharding,
+            singleton_jobs: self.singleton_jobs,
+            priority_fair_share_percentage: self.priority_fair_share_percentage,
+            tenant_fairness: self.tenant_fairness,
+            processing_mode: self.processing_mode,
+            unordered_query_limit: self.unordered_query_limit,
+            unordered_http_concurrency: self.unordered_http_concurrency,
+            expired_outbox_action: Some(expired_outbox_action),
+            http_gateway: self.http_gateway,
         }
     }
 }
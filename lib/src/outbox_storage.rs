@@ -0,0 +1,61 @@
+#[cfg(feature = "mysql")]
+use crate::mysql_outbox_storage::MySqlOutboxStorage;
+use crate::outbox_storage_backend::OutboxStorageBackend;
+use crate::postgres_outbox_storage::PostgresOutboxStorage;
+#[cfg(feature = "sqlite")]
+use crate::sqlite_outbox_storage::SqliteOutboxStorage;
+#[cfg(feature = "mysql")]
+use sqlx::MySql;
+#[cfg(feature = "sqlite")]
+use sqlx::Sqlite;
+use sqlx::{Pool, Postgres};
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub enum OutboxStorage {
+    Postgres(Pool<Postgres>),
+    #[cfg(feature = "mysql")]
+    MySql(Pool<MySql>),
+    #[cfg(feature = "sqlite")]
+    Sqlite(Pool<Sqlite>),
+}
+
+impl OutboxStorage {
+    pub fn postgres_pool(&self) -> Option<&Pool<Postgres>> {
+        match self {
+            OutboxStorage::Postgres(pool) => Some(pool),
+            #[cfg(any(feature = "mysql", feature = "sqlite"))]
+            _ => None,
+        }
+    }
+
+    pub(crate) fn backend(&self) -> Arc<dyn OutboxStorageBackend> {
+        match self {
+            OutboxStorage::Postgres(pool) => Arc::new(PostgresOutboxStorage::new(pool.clone())),
+            #[cfg(feature = "mysql")]
+            OutboxStorage::MySql(pool) => Arc::new(MySqlOutboxStorage::new(pool.clone())),
+            #[cfg(feature = "sqlite")]
+            OutboxStorage::Sqlite(pool) => Arc::new(SqliteOutboxStorage::new(pool.clone())),
+        }
+    }
+}
+
+impl From<Pool<Postgres>> for OutboxStorage {
+    fn from(pool: Pool<Postgres>) -> Self {
+        OutboxStorage::Postgres(pool)
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<Pool<MySql>> for OutboxStorage {
+    fn from(pool: Pool<MySql>) -> Self {
+        OutboxStorage::MySql(pool)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<Pool<Sqlite>> for OutboxStorage {
+    fn from(pool: Pool<Sqlite>) -> Self {
+        OutboxStorage::Sqlite(pool)
+    }
+}
@@ -0,0 +1,173 @@
+use crate::app_state::AppState;
+use crate::error::OutboxPatternProcessorError;
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+use crate::lock_strategy::LockStrategy;
+use crate::outbox::Outbox;
+use crate::outbox_backlog::OutboxBacklog;
+use crate::outbox_job::OutboxJob;
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+use crate::processing_mode::ProcessingMode;
+use crate::registered_destination::RegisteredDestination;
+use async_trait::async_trait;
+use sqlx::types::chrono::{DateTime, Utc};
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+use sqlx::{Database, Encode, QueryBuilder, Type};
+use uuid::Uuid;
+
+pub const STUCK_PARTITION_KEYS_LIMIT: i64 = 100;
+
+#[async_trait]
+pub trait OutboxStorageBackend: Send + Sync {
+    async fn list(
+        &self,
+        app_state: &AppState,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError>;
+
+    async fn renew_lease(
+        &self,
+        app_state: &AppState,
+    ) -> Result<bool, OutboxPatternProcessorError>;
+
+    async fn complete(
+        &self,
+        app_state: &AppState,
+        successfully_outboxes: &[Outbox],
+        failure_outboxes: &[Outbox],
+        invalid_outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError>;
+
+    async fn find_registered_destinations(&self) -> Result<Vec<RegisteredDestination>, OutboxPatternProcessorError>;
+
+    async fn clear_processed_locked_partition_key(&self) -> Result<(), OutboxPatternProcessorError>;
+
+    async fn try_acquire_leadership(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+        lease_duration_in_seconds: u64,
+    ) -> Result<bool, OutboxPatternProcessorError>;
+
+    async fn release_leadership(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+    ) -> Result<(), OutboxPatternProcessorError>;
+
+    async fn find_leadership_last_run_at(
+        &self,
+        name: &str,
+    ) -> Result<Option<DateTime<Utc>>, OutboxPatternProcessorError>;
+
+    async fn record_leadership_run(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+        last_run_at: DateTime<Utc>,
+    ) -> Result<(), OutboxPatternProcessorError>;
+
+    async fn purge_processed_locks(&self) -> Result<u64, OutboxPatternProcessorError>;
+
+    async fn purge_processed_outboxes(
+        &self,
+        retention_in_seconds: u64,
+    ) -> Result<u64, OutboxPatternProcessorError>;
+
+    async fn find_backlog(
+        &self,
+        outbox_failure_limit: u32,
+    ) -> Result<OutboxBacklog, OutboxPatternProcessorError>;
+
+    async fn find_stuck_partition_keys(
+        &self,
+        outbox_failure_limit: u32,
+        threshold_in_seconds: u64,
+    ) -> Result<Vec<Uuid>, OutboxPatternProcessorError>;
+
+    async fn save_backlog_snapshot(
+        &self,
+        backlog: &OutboxBacklog,
+    ) -> Result<(), OutboxPatternProcessorError>;
+
+    async fn find_vacuum_analyze_hints(
+        &self,
+        dead_tuple_ratio: f64,
+    ) -> Result<Vec<String>, OutboxPatternProcessorError>;
+
+    async fn find_jobs(&self) -> Result<Vec<OutboxJob>, OutboxPatternProcessorError>;
+
+    async fn save_job(
+        &self,
+        job: &OutboxJob,
+    ) -> Result<(), OutboxPatternProcessorError>;
+
+    async fn delete_job(
+        &self,
+        name: &str,
+    ) -> Result<bool, OutboxPatternProcessorError>;
+
+    async fn record_job_run(
+        &self,
+        name: &str,
+        last_run_at: DateTime<Utc>,
+        last_result: &str,
+        last_duration_in_millis: i64,
+    ) -> Result<(), OutboxPatternProcessorError>;
+}
+
+pub fn priority_fair_share_limit(app_state: &AppState) -> i64 {
+    let query_limit = app_state.outbox_query_limit.unwrap_or(50) as u64;
+    let percentage = app_state.priority_fair_share_percentage.unwrap_or(10).min(100) as u64;
+
+    (query_limit * percentage).div_ceil(100) as i64
+}
+
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+pub fn ensure_supported_options(
+    app_state: &AppState,
+    backend: &str,
+) -> Result<(), OutboxPatternProcessorError> {
+    let mut unsupported_options = vec![];
+
+    if app_state.lock_strategy.is_some_and(|lock_strategy| lock_strategy != LockStrategy::LockTable) {
+        unsupported_options.push("lock_strategy");
+    }
+    if app_state.worker_sharding.is_some() {
+        unsupported_options.push("worker_sharding");
+    }
+    if app_state.tenant_fairness.is_some() {
+        unsupported_options.push("tenant_fairness");
+    }
+    if app_state.processing_mode.is_some_and(|processing_mode| processing_mode != ProcessingMode::Ordered) {
+        unsupported_options.push("processing_mode");
+    }
+
+    if unsupported_options.is_empty() {
+        Ok(())
+    } else {
+        Err(OutboxPatternProcessorError::new(
+            &format!("Unsupported options on {}: {}", backend, unsupported_options.join(", ")),
+            "Invalid outbox processor configuration",
+        ))
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+pub fn push_ids<'args, DB>(
+    query_builder: &mut QueryBuilder<'args, DB>,
+    ids: &[Uuid],
+) where
+    DB: Database,
+    Uuid: Encode<'args, DB> + Type<DB>,
+{
+    if ids.is_empty() {
+        query_builder.push("(null)");
+        return;
+    }
+
+    let mut separated = query_builder.separated(", ");
+    separated.push_unseparated("(");
+    for id in ids {
+        separated.push_bind(*id);
+    }
+    separated.push_unseparated(")");
+}
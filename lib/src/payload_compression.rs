@@ -0,0 +1,76 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadCompression {
+    Gzip,
+    Zstd,
+}
+
+impl PayloadCompression {
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            PayloadCompression::Gzip => "gzip",
+            PayloadCompression::Zstd => "zstd",
+        }
+    }
+
+    pub fn from_content_encoding(content_encoding: &str) -> Result<Self, OutboxPatternProcessorError> {
+        match content_encoding.to_lowercase().as_str() {
+            "gzip" => Ok(PayloadCompression::Gzip),
+            "zstd" => Ok(PayloadCompression::Zstd),
+            _ => Err(OutboxPatternProcessorError::new(
+                &format!("Unsupported content encoding {content_encoding}"),
+                "Failed to resolve payload compression",
+            )),
+        }
+    }
+
+    pub fn wrap(
+        &self,
+        outbox: &Outbox,
+    ) -> Result<Outbox, OutboxPatternProcessorError> {
+        Ok(Outbox {
+            payload: String::new(),
+            payload_binary: Some(self.compress(&outbox.body_bytes())?),
+            content_encoding: Some(self.content_encoding().to_string()),
+            ..outbox.clone()
+        })
+    }
+
+    pub fn compress(
+        &self,
+        bytes: &[u8],
+    ) -> Result<Vec<u8>, OutboxPatternProcessorError> {
+        let result = match self {
+            PayloadCompression::Gzip => {
+                let mut encoder = GzEncoder::new(vec![], Compression::default());
+                encoder.write_all(bytes).and_then(|_| encoder.finish())
+            },
+            PayloadCompression::Zstd => zstd::encode_all(bytes, 0),
+        };
+
+        result.map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to compress payload with {}", self.content_encoding())))
+    }
+
+    pub fn decompress(
+        &self,
+        bytes: &[u8],
+    ) -> Result<Vec<u8>, OutboxPatternProcessorError> {
+        let result = match self {
+            PayloadCompression::Gzip => {
+                let mut decompressed = vec![];
+                GzDecoder::new(bytes).read_to_end(&mut decompressed).map(|_| decompressed)
+            },
+            PayloadCompression::Zstd => zstd::decode_all(bytes),
+        };
+
+        result.map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to decompress payload with {}", self.content_encoding())))
+    }
+}
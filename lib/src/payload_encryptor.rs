@@ -0,0 +1,216 @@
+use crate::data_key_provider::{DataKey, DataKeyProvider};
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::payload_envelope::PayloadEnvelope;
+use aes_gcm::aead::{Aead, OsRng, Payload};
+use aes_gcm::{AeadCore, Aes256Gcm, Key, KeyInit, Nonce};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const NONCE_LENGTH: usize = 12;
+const DEFAULT_DATA_KEY_CACHE_TTL_IN_SECONDS: u64 = 300;
+
+#[derive(Clone)]
+struct CachedDataKey {
+    data_key: DataKey,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+struct CachedPlaintextKey {
+    plaintext: Vec<u8>,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct PayloadEncryptor {
+    pub data_key_provider: Arc<dyn DataKeyProvider>,
+    data_key_cache_ttl_in_seconds: u64,
+    encryption_key: Arc<Mutex<Option<CachedDataKey>>>,
+    decryption_keys: Arc<Mutex<HashMap<String, CachedPlaintextKey>>>,
+}
+
+impl PayloadEncryptor {
+    pub fn new<P: DataKeyProvider + 'static>(data_key_provider: P) -> Self {
+        Self {
+            data_key_provider: Arc::new(data_key_provider),
+            data_key_cache_ttl_in_seconds: DEFAULT_DATA_KEY_CACHE_TTL_IN_SECONDS,
+            encryption_key: Arc::new(Mutex::new(None)),
+            decryption_keys: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn with_data_key_cache_ttl_in_seconds(
+        self,
+        data_key_cache_ttl_in_seconds: u64,
+    ) -> Self {
+        Self {
+            data_key_provider: self.data_key_provider,
+            data_key_cache_ttl_in_seconds,
+            encryption_key: self.encryption_key,
+            decryption_keys: self.decryption_keys,
+        }
+    }
+
+    async fn encryption_data_key(&self) -> Result<DataKey, OutboxPatternProcessorError> {
+        let mut encryption_key = self.encryption_key.lock().await;
+
+        if let Some(cached) = encryption_key.as_ref() {
+            if cached.expires_at > Utc::now() {
+                return Ok(cached.data_key.clone());
+            }
+        }
+
+        let data_key = self.data_key_provider.generate_data_key().await?;
+        *encryption_key = Some(CachedDataKey {
+            data_key: data_key.clone(),
+            expires_at: Utc::now() + Duration::from_secs(self.data_key_cache_ttl_in_seconds),
+        });
+
+        Ok(data_key)
+    }
+
+    async fn decryption_data_key(
+        &self,
+        envelope: &PayloadEnvelope,
+        encrypted_key: &[u8],
+    ) -> Result<Vec<u8>, OutboxPatternProcessorError> {
+        let cache_key = format!("{}:{}", envelope.key_id, envelope.encrypted_key);
+
+        if let Some(cached) = self.decryption_keys.lock().await.get(&cache_key) {
+            if cached.expires_at > Utc::now() {
+                return Ok(cached.plaintext.clone());
+            }
+        }
+
+        let plaintext = self.data_key_provider.decrypt_data_key(&envelope.key_id, encrypted_key).await?;
+
+        let now = Utc::now();
+        let mut decryption_keys = self.decryption_keys.lock().await;
+        decryption_keys.retain(|_, cached| cached.expires_at > now);
+        decryption_keys.insert(
+            cache_key,
+            CachedPlaintextKey {
+                plaintext: plaintext.clone(),
+                expires_at: now + Duration::from_secs(self.data_key_cache_ttl_in_seconds),
+            },
+        );
+
+        Ok(plaintext)
+    }
+
+    pub async fn encrypt(
+        &self,
+        outbox: &Outbox,
+    ) -> Result<Outbox, OutboxPatternProcessorError> {
+        if outbox.encryption.is_some() {
+            return Ok(outbox.clone());
+        }
+
+        let data_key = self.encryption_data_key().await?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let body = outbox.body_bytes();
+
+        let encrypted = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key.plaintext))
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: body.as_slice(),
+                    aad: outbox.idempotent_key.as_bytes(),
+                },
+            )
+            .map_err(|error| {
+                OutboxPatternProcessorError::new(
+                    &error.to_string(),
+                    &format!("Failed to encrypt payload for outbox idempotent_key={}", outbox.idempotent_key),
+                )
+            })?;
+
+        Ok(Outbox {
+            payload: if outbox.is_binary() { String::new() } else { BASE64_STANDARD.encode(&encrypted) },
+            payload_binary: if outbox.is_binary() { Some(encrypted) } else { None },
+            payload_json: None,
+            encryption: Some(Json(PayloadEnvelope {
+                key_id: data_key.key_id,
+                encrypted_key: BASE64_STANDARD.encode(&data_key.encrypted),
+                nonce: BASE64_STANDARD.encode(nonce),
+            })),
+            ..outbox.clone()
+        })
+    }
+
+    pub async fn decrypt(
+        &self,
+        outbox: &Outbox,
+    ) -> Result<Outbox, OutboxPatternProcessorError> {
+        let envelope = match &outbox.encryption {
+            Some(envelope) => envelope.0.clone(),
+            None => return Ok(outbox.clone()),
+        };
+
+        let decode = |value: &str| {
+            BASE64_STANDARD.decode(value).map_err(|error| {
+                OutboxPatternProcessorError::new(
+                    &error.to_string(),
+                    &format!("Failed to decode encrypted payload for outbox idempotent_key={}", outbox.idempotent_key),
+                )
+            })
+        };
+
+        let data_key = self.decryption_data_key(&envelope, &decode(&envelope.encrypted_key)?).await?;
+        let encrypted = match &outbox.payload_binary {
+            Some(bytes) => bytes.clone(),
+            None => decode(&outbox.payload)?,
+        };
+
+        let nonce = decode(&envelope.nonce)?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(OutboxPatternProcessorError::new(
+                "Invalid nonce length",
+                &format!("Failed to decrypt payload for outbox idempotent_key={}", outbox.idempotent_key),
+            ));
+        }
+
+        let decrypted = Aes256Gcm::new_from_slice(&data_key)
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Invalid data key length"))?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: encrypted.as_slice(),
+                    aad: outbox.idempotent_key.as_bytes(),
+                },
+            )
+            .map_err(|error| {
+                OutboxPatternProcessorError::new(
+                    &error.to_string(),
+                    &format!("Failed to decrypt payload for outbox idempotent_key={}", outbox.idempotent_key),
+                )
+            })?;
+
+        if outbox.is_binary() {
+            Ok(Outbox {
+                payload_binary: Some(decrypted),
+                encryption: None,
+                ..outbox.clone()
+            })
+        } else {
+            let payload = String::from_utf8(decrypted).map_err(|error| {
+                OutboxPatternProcessorError::new(
+                    &error.to_string(),
+                    &format!("Failed to decode decrypted payload for outbox idempotent_key={}", outbox.idempotent_key),
+                )
+            })?;
+
+            Ok(Outbox {
+                payload,
+                encryption: None,
+                ..outbox.clone()
+            })
+        }
+    }
+}
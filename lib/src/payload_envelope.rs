@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PayloadEnvelope {
+    pub key_id: String,
+    pub encrypted_key: String,
+    pub nonce: String,
+}
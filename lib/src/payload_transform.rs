@@ -0,0 +1,169 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use handlebars::{handlebars_helper, Handlebars};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use serde_json_path::JsonPath;
+use std::collections::BTreeMap;
+
+handlebars_helper!(json_helper: |value: Json| value.to_string());
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PayloadTransform {
+    Template { template: String },
+    JsonPath { fields: BTreeMap<String, String> },
+    AllowList { fields: Vec<String> },
+    DenyList { fields: Vec<String> },
+}
+
+impl PayloadTransform {
+    pub fn apply_all(
+        transforms: &Option<Vec<PayloadTransform>>,
+        outbox: &Outbox,
+    ) -> Result<Outbox, OutboxPatternProcessorError> {
+        if outbox.is_binary() && transforms.iter().flatten().next().is_some() {
+            return Err(OutboxPatternProcessorError::new(
+                "Payload transforms require a text payload",
+                &format!("Failed to transform binary payload for outbox idempotent_key={}", outbox.idempotent_key),
+            ));
+        }
+
+        let mut transformed_outbox = outbox.clone();
+
+        for transform in transforms.iter().flatten() {
+            transformed_outbox.payload = transform.apply(&transformed_outbox)?;
+        }
+
+        Ok(transformed_outbox)
+    }
+
+    pub fn apply(
+        &self,
+        outbox: &Outbox,
+    ) -> Result<String, OutboxPatternProcessorError> {
+        match self {
+            PayloadTransform::Template { template } => Self::render_template(template, outbox),
+            PayloadTransform::JsonPath { fields } => Self::project_json_path(fields, outbox),
+            PayloadTransform::AllowList { fields } => {
+                let payload = Self::json_payload(outbox)?;
+                let mut allowed = Value::Object(Map::new());
+                for field in fields {
+                    let path = field.split('.').collect::<Vec<&str>>();
+                    if let Some(value) = Self::get_path(&payload, &path) {
+                        Self::set_path(&mut allowed, &path, value.clone());
+                    }
+                }
+
+                Ok(allowed.to_string())
+            },
+            PayloadTransform::DenyList { fields } => {
+                let mut payload = Self::json_payload(outbox)?;
+                for field in fields {
+                    Self::remove_path(&mut payload, &field.split('.').collect::<Vec<&str>>());
+                }
+
+                Ok(payload.to_string())
+            },
+        }
+    }
+
+    fn project_json_path(
+        fields: &BTreeMap<String, String>,
+        outbox: &Outbox,
+    ) -> Result<String, OutboxPatternProcessorError> {
+        let payload = Self::json_payload(outbox)?;
+
+        let mut projected = Map::new();
+        for (field, path) in fields {
+            let json_path = JsonPath::parse(path).map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to parse json path {path}")))?;
+
+            let mut values = json_path.query(&payload).all().into_iter().cloned().collect::<Vec<Value>>();
+            let value = match values.len() {
+                0 => Value::Null,
+                1 => values.remove(0),
+                _ => Value::Array(values),
+            };
+
+            projected.insert(field.clone(), value);
+        }
+
+        Ok(Value::Object(projected).to_string())
+    }
+
+    fn render_template(
+        template: &str,
+        outbox: &Outbox,
+    ) -> Result<String, OutboxPatternProcessorError> {
+        let mut handlebars = Handlebars::new();
+        handlebars.register_escape_fn(handlebars::no_escape);
+        handlebars.register_helper("json", Box::new(json_helper));
+
+        let payload = serde_json::from_str::<Value>(&outbox.payload).unwrap_or(Value::String(outbox.payload.clone()));
+        let context = json!({
+            "payload": payload,
+            "headers": outbox.headers.as_ref().map(|headers| headers.0.clone()).unwrap_or_default(),
+            "idempotent_key": outbox.idempotent_key,
+            "partition_key": outbox.partition_key,
+            "created_at": outbox.created_at.to_rfc3339(),
+        });
+
+        handlebars.render_template(template, &context).map_err(|error| {
+            OutboxPatternProcessorError::new(
+                &error.to_string(),
+                &format!("Failed to render payload template for outbox idempotent_key={}", outbox.idempotent_key),
+            )
+        })
+    }
+
+    fn json_payload(outbox: &Outbox) -> Result<Value, OutboxPatternProcessorError> {
+        serde_json::from_str::<Value>(&outbox.payload).map_err(|error| {
+            OutboxPatternProcessorError::new(
+                &error.to_string(),
+                &format!("Failed to parse payload as json for outbox idempotent_key={}", outbox.idempotent_key),
+            )
+        })
+    }
+
+    fn get_path<'a>(
+        value: &'a Value,
+        path: &[&str],
+    ) -> Option<&'a Value> {
+        path.iter().try_fold(value, |current, key| current.get(key))
+    }
+
+    fn set_path(
+        value: &mut Value,
+        path: &[&str],
+        new_value: Value,
+    ) {
+        if let Some((last, parents)) = path.split_last() {
+            let mut current = value;
+            for key in parents {
+                if !current.get(key).map(Value::is_object).unwrap_or(false) {
+                    current[key] = Value::Object(Map::new());
+                }
+                current = &mut current[key];
+            }
+            current[last] = new_value;
+        }
+    }
+
+    fn remove_path(
+        value: &mut Value,
+        path: &[&str],
+    ) {
+        if let Some((last, parents)) = path.split_last() {
+            let mut current = value;
+            for key in parents {
+                match current.get_mut(key) {
+                    Some(next) => current = next,
+                    None => return,
+                }
+            }
+            if let Some(object) = current.as_object_mut() {
+                object.remove(*last);
+            }
+        }
+    }
+}
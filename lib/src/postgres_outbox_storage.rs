@@ -0,0 +1,1076 @@
+use crate::app_state::AppState;
+use crate::error::OutboxPatternProcessorError;
+use crate::lock_strategy::LockStrategy;
+use crate::outbox::Outbox;
+use crate::outbox_backlog::OutboxBacklog;
+use crate::outbox_cleaner_schedule::OutboxCleanerSchedule;
+use crate::outbox_job::OutboxJob;
+use crate::outbox_storage_backend::{priority_fair_share_limit, OutboxStorageBackend, STUCK_PARTITION_KEYS_LIMIT};
+use crate::processing_mode::ProcessingMode;
+use crate::registered_destination::RegisteredDestination;
+use crate::tenant_fairness::TenantKey;
+use crate::worker_sharding::WorkerSharding;
+use async_trait::async_trait;
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, Transaction};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::instrument;
+use uuid::Uuid;
+
+const ADVISORY_LOCK_CANDIDATES_FACTOR: u32 = 4;
+
+struct TenantFairnessParams {
+    header: Option<String>,
+    max_share_limit: i64,
+    tenants: Vec<String>,
+    weights: Vec<i32>,
+    default_weight: i32,
+}
+
+impl TenantFairnessParams {
+    fn from_app_state(app_state: &AppState) -> Self {
+        match &app_state.tenant_fairness {
+            Some(tenant_fairness) => {
+                let (tenants, weights) = tenant_fairness
+                    .weights
+                    .iter()
+                    .map(|(tenant, weight)| (tenant.clone(), (*weight).clamp(1, i32::MAX as u32) as i32))
+                    .unzip();
+
+                Self {
+                    header: match &tenant_fairness.tenant_key {
+                        TenantKey::Column => None,
+                        TenantKey::Header(header) => Some(header.clone()),
+                    },
+                    max_share_limit: tenant_fairness.max_share_limit(app_state.outbox_query_limit.unwrap_or(50)),
+                    tenants,
+                    weights,
+                    default_weight: tenant_fairness.default_weight.clamp(1, i32::MAX as u32) as i32,
+                }
+            },
+            None => Self {
+                header: None,
+                max_share_limit: i64::MAX,
+                tenants: vec![],
+                weights: vec![],
+                default_weight: i32::MAX,
+            },
+        }
+    }
+}
+
+pub struct PostgresOutboxStorage {
+    postgres_pool: Pool<Postgres>,
+    claim_transaction: Mutex<Option<Transaction<'static, Postgres>>>,
+    lease: Mutex<Option<(Uuid, Vec<Uuid>)>>,
+}
+
+#[async_trait]
+impl OutboxStorageBackend for PostgresOutboxStorage {
+    #[instrument(skip_all, name = "lock_and_get_outboxes")]
+    async fn list(
+        &self,
+        app_state: &AppState,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        let processing_mode = app_state.processing_mode.unwrap_or_default();
+
+        let mut outboxes = if processing_mode == ProcessingMode::Unordered {
+            vec![]
+        } else {
+            self.list_ordered(app_state).await?
+        };
+
+        if processing_mode != ProcessingMode::Ordered {
+            outboxes.extend(self.list_unordered(app_state).await?);
+        }
+
+        Ok(outboxes)
+    }
+
+    #[instrument(skip_all)]
+    async fn renew_lease(
+        &self,
+        app_state: &AppState,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let (lock_id, partition_keys) = match self.lease.lock().await.clone() {
+            Some(lease) if !lease.1.is_empty() => lease,
+            _ => return Ok(true),
+        };
+
+        let sql = "update outbox_lock set processing_until = now() + ($3)::interval where lock_id = $1 and partition_key = ANY($2) and processed_at is null";
+
+        let result = sqlx::query(sql)
+            .bind(lock_id)
+            .bind(&partition_keys)
+            .bind(format!("{} seconds", app_state.max_in_flight_interval_in_seconds.unwrap_or(30)))
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to renew partition keys lease"))?;
+
+        Ok(result.rows_affected() as usize == partition_keys.len())
+    }
+
+    #[instrument(skip_all)]
+    async fn complete(
+        &self,
+        app_state: &AppState,
+        successfully_outboxes: &[Outbox],
+        failure_outboxes: &[Outbox],
+        invalid_outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let claim_transaction = self.claim_transaction.lock().await.take();
+
+        let mut transaction = match claim_transaction {
+            Some(transaction) => transaction,
+            None => self.begin_transaction().await?,
+        };
+
+        if app_state.delete_after_process_successfully.unwrap_or(false) {
+            Self::delete_processed(app_state, &mut transaction, successfully_outboxes).await?;
+        } else {
+            Self::mark_as_processed(app_state, &mut transaction, successfully_outboxes).await?;
+        }
+
+        if !failure_outboxes.is_empty() {
+            Self::increase_attempts(app_state, &mut transaction, failure_outboxes).await?;
+        }
+
+        if !invalid_outboxes.is_empty() {
+            Self::dead_letter(app_state, &mut transaction, invalid_outboxes).await?;
+        }
+
+        if app_state.lock_strategy == Some(LockStrategy::PartitionTable) {
+            let partition_keys = successfully_outboxes
+                .iter()
+                .chain(failure_outboxes)
+                .chain(invalid_outboxes)
+                .filter(|it| !it.unordered)
+                .map(|it| it.partition_key)
+                .collect::<Vec<Uuid>>();
+
+            Self::clear_drained_partitions(&mut transaction, &partition_keys).await?;
+        }
+
+        Self::commit_transaction(transaction).await
+    }
+
+    #[instrument(skip_all)]
+    async fn find_registered_destinations(&self) -> Result<Vec<RegisteredDestination>, OutboxPatternProcessorError> {
+        let sql = "select name, destination from outbox_destination";
+
+        sqlx::query_as(sql)
+            .fetch_all(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list registered destinations"))
+    }
+
+    #[instrument(skip_all)]
+    async fn clear_processed_locked_partition_key(&self) -> Result<(), OutboxPatternProcessorError> {
+        let mut transaction = self.begin_transaction().await?;
+
+        if let Some(outbox_clear_schedule) = Self::find_cleaner_schedule(&mut transaction).await? {
+            if outbox_clear_schedule.is_due() {
+                Self::clear_processed_locks(&mut transaction).await?;
+                Self::update_last_cleaner_execution(&mut transaction).await?;
+                Self::commit_transaction(transaction).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn try_acquire_leadership(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+        lease_duration_in_seconds: u64,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let sql = r#"insert into outbox_leader (name, holder_id, lease_until)
+values ($1, $2, now() + ($3)::interval)
+on conflict (name) do update set holder_id = excluded.holder_id, lease_until = excluded.lease_until
+where outbox_leader.holder_id = excluded.holder_id or outbox_leader.lease_until <= now()
+returning holder_id"#;
+
+        let leader: Option<Uuid> = sqlx::query_scalar(sql)
+            .bind(name)
+            .bind(holder_id)
+            .bind(format!("{lease_duration_in_seconds} seconds"))
+            .fetch_optional(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to acquire leadership"))?;
+
+        Ok(leader.is_some())
+    }
+
+    #[instrument(skip_all)]
+    async fn release_leadership(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        sqlx::query("update outbox_leader set lease_until = now() where name = $1 and holder_id = $2")
+            .bind(name)
+            .bind(holder_id)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to release leadership"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn find_leadership_last_run_at(
+        &self,
+        name: &str,
+    ) -> Result<Option<DateTime<Utc>>, OutboxPatternProcessorError> {
+        let last_run_at: Option<Option<DateTime<Utc>>> = sqlx::query_scalar("select last_run_at from outbox_leader where name = $1")
+            .bind(name)
+            .fetch_optional(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to find leadership last run name={name}")))?;
+
+        Ok(last_run_at.flatten())
+    }
+
+    #[instrument(skip_all)]
+    async fn record_leadership_run(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+        last_run_at: DateTime<Utc>,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        sqlx::query("update outbox_leader set last_run_at = $3 where name = $1 and holder_id = $2")
+            .bind(name)
+            .bind(holder_id)
+            .bind(last_run_at)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to record leadership run name={name}")))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn purge_processed_locks(&self) -> Result<u64, OutboxPatternProcessorError> {
+        let sql = "delete from outbox_lock where processed_at is not null and processed_at < now()";
+
+        let result = sqlx::query(sql)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to clear processed locks"))?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip_all)]
+    async fn purge_processed_outboxes(
+        &self,
+        retention_in_seconds: u64,
+    ) -> Result<u64, OutboxPatternProcessorError> {
+        let sql = "delete from outbox where processed_at is not null and processed_at < now() - ($1)::interval";
+
+        let result = sqlx::query(sql)
+            .bind(format!("{retention_in_seconds} seconds"))
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to delete processed outboxes"))?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip_all)]
+    async fn find_backlog(
+        &self,
+        outbox_failure_limit: u32,
+    ) -> Result<OutboxBacklog, OutboxPatternProcessorError> {
+        let sql = r#"select
+    count(1) filter (where attempts < $1 and dead_lettered_at is null) as pending,
+    count(1) filter (where attempts >= $1 or dead_lettered_at is not null) as dead_lettered,
+    min(process_after) filter (where attempts < $1 and dead_lettered_at is null) as oldest_pending_at
+from outbox
+where processed_at is null"#;
+
+        sqlx::query_as(sql)
+            .bind(outbox_failure_limit as i32)
+            .fetch_one(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to find outbox backlog"))
+    }
+
+    #[instrument(skip_all)]
+    async fn find_stuck_partition_keys(
+        &self,
+        outbox_failure_limit: u32,
+        threshold_in_seconds: u64,
+    ) -> Result<Vec<Uuid>, OutboxPatternProcessorError> {
+        let sql = r#"select partition_key
+from outbox
+where processed_at is null
+    and attempts < $1
+    and dead_lettered_at is null
+group by partition_key
+having min(process_after) < now() - ($2)::interval
+order by min(process_after)
+limit $3"#;
+
+        sqlx::query_scalar(sql)
+            .bind(outbox_failure_limit as i32)
+            .bind(format!("{threshold_in_seconds} seconds"))
+            .bind(STUCK_PARTITION_KEYS_LIMIT)
+            .fetch_all(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to find stuck partition keys"))
+    }
+
+    #[instrument(skip_all)]
+    async fn save_backlog_snapshot(
+        &self,
+        backlog: &OutboxBacklog,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "insert into outbox_backlog_snapshot (pending, dead_lettered, oldest_pending_at) values ($1, $2, $3)";
+
+        sqlx::query(sql)
+            .bind(backlog.pending)
+            .bind(backlog.dead_lettered)
+            .bind(backlog.oldest_pending_at)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to save outbox backlog snapshot"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn find_vacuum_analyze_hints(
+        &self,
+        dead_tuple_ratio: f64,
+    ) -> Result<Vec<String>, OutboxPatternProcessorError> {
+        let sql = r#"select relname::text, n_live_tup, n_dead_tup, n_mod_since_analyze
+from pg_stat_user_tables
+where relname in ('outbox', 'outbox_lock', 'outbox_partition')
+order by relname"#;
+
+        let statistics: Vec<(String, i64, i64, i64)> = sqlx::query_as(sql)
+            .fetch_all(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to find outbox tables statistics"))?;
+
+        let mut hints = vec![];
+        for (table, live_tuples, dead_tuples, modified_since_analyze) in statistics {
+            let threshold = live_tuples.max(1) as f64 * dead_tuple_ratio;
+
+            if dead_tuples as f64 > threshold {
+                hints.push(format!("VACUUM {table}: dead_tuples={dead_tuples}, live_tuples={live_tuples}"));
+            }
+
+            if modified_since_analyze as f64 > threshold {
+                hints.push(format!("ANALYZE {table}: modified_since_analyze={modified_since_analyze}, live_tuples={live_tuples}"));
+            }
+        }
+
+        Ok(hints)
+    }
+
+    #[instrument(skip_all)]
+    async fn find_jobs(&self) -> Result<Vec<OutboxJob>, OutboxPatternProcessorError> {
+        sqlx::query_as("select * from outbox_job order by name")
+            .fetch_all(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list outbox jobs"))
+    }
+
+    #[instrument(skip_all)]
+    async fn save_job(
+        &self,
+        job: &OutboxJob,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = r#"insert into outbox_job (name, kind, cron_expression, enabled)
+values ($1, $2, $3, $4)
+on conflict (name) do update set kind = excluded.kind, cron_expression = excluded.cron_expression, enabled = excluded.enabled"#;
+
+        sqlx::query(sql)
+            .bind(&job.name)
+            .bind(&job.kind)
+            .bind(&job.cron_expression)
+            .bind(job.enabled)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to save outbox job name={}", job.name)))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn delete_job(
+        &self,
+        name: &str,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let result = sqlx::query("delete from outbox_job where name = $1")
+            .bind(name)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to delete outbox job name={name}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip_all)]
+    async fn record_job_run(
+        &self,
+        name: &str,
+        last_run_at: DateTime<Utc>,
+        last_result: &str,
+        last_duration_in_millis: i64,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "update outbox_job set last_run_at = $2, last_result = $3, last_duration_in_millis = $4 where name = $1";
+
+        sqlx::query(sql)
+            .bind(name)
+            .bind(last_run_at)
+            .bind(last_result)
+            .bind(last_duration_in_millis)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to record outbox job run name={name}")))?;
+
+        Ok(())
+    }
+}
+
+impl PostgresOutboxStorage {
+    pub fn new(postgres_pool: Pool<Postgres>) -> Self {
+        Self {
+            postgres_pool,
+            claim_transaction: Mutex::new(None),
+            lease: Mutex::new(None),
+        }
+    }
+
+    #[instrument(skip_all)]
+    async fn list_ordered(
+        &self,
+        app_state: &AppState,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        let lock_strategy = app_state.lock_strategy.unwrap_or_default();
+        let (shard_index, shard_count) = self.resolve_shard(app_state).await?;
+
+        if lock_strategy == LockStrategy::LockTable {
+            let lock_id = Uuid::now_v7();
+            let processing_until_incremente_interval = format!("{} seconds", app_state.max_in_flight_interval_in_seconds.unwrap_or(30));
+
+            self.lock_partition_key(app_state, lock_id, processing_until_incremente_interval, shard_index, shard_count)
+                .await?;
+
+            let outboxes = self.get_outboxes_ranked_from_locked_partition_key(app_state, lock_id).await?;
+            *self.lease.lock().await = Some((lock_id, outboxes.iter().map(|it| it.partition_key).collect()));
+
+            return Ok(outboxes);
+        }
+
+        let mut transaction = self.begin_transaction().await?;
+
+        let partition_keys = if lock_strategy == LockStrategy::AdvisoryLock {
+            Self::lock_partition_key_with_advisory_lock(app_state, &mut transaction, shard_index, shard_count).await?
+        } else {
+            Self::lock_partition_key_with_partition_table(app_state, &mut transaction, shard_index, shard_count).await?
+        };
+
+        if partition_keys.is_empty() {
+            Self::commit_transaction(transaction).await?;
+            return Ok(vec![]);
+        }
+
+        let outboxes = Self::get_outboxes_ranked_from_partition_keys(app_state, &mut transaction, &partition_keys).await?;
+
+        *self.claim_transaction.lock().await = Some(transaction);
+
+        Ok(outboxes)
+    }
+
+    #[instrument(skip_all)]
+    async fn list_unordered(
+        &self,
+        app_state: &AppState,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        let sql_list = r#"select o.*
+from outbox o
+where o.unordered
+    and o.processed_at is null
+    and o.process_after < now()
+    and o.attempts < $2
+    and o.dead_lettered_at is null
+order by o.priority desc, o.process_after
+limit $1
+for update skip locked"#;
+
+        let mut claim_transaction = self.claim_transaction.lock().await;
+        let claimed_ordered = claim_transaction.is_some();
+        let mut transaction = match claim_transaction.take() {
+            Some(transaction) => transaction,
+            None => self.begin_transaction().await?,
+        };
+
+        let outboxes: Vec<Outbox> = sqlx::query_as(sql_list)
+            .bind(app_state.unordered_query_limit.unwrap_or(500) as i32)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .fetch_all(&mut *transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to lock unordered outboxes"))?;
+
+        if outboxes.is_empty() && !claimed_ordered {
+            Self::commit_transaction(transaction).await?;
+        } else {
+            *claim_transaction = Some(transaction);
+        }
+
+        Ok(outboxes)
+    }
+
+    async fn begin_transaction(&self) -> Result<Transaction<'static, Postgres>, OutboxPatternProcessorError> {
+        self.postgres_pool
+            .begin()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to create transaction"))
+    }
+
+    async fn commit_transaction(transaction: Transaction<'_, Postgres>) -> Result<(), OutboxPatternProcessorError> {
+        transaction
+            .commit()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to commit transaction"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn get_outboxes_ranked_from_locked_partition_key(
+        &self,
+        app_state: &AppState,
+        lock_id: Uuid,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        let sql_list = r#"with locked as (
+    select
+        o.idempotent_key,
+        row_number() over (partition by o.partition_key order by o.process_after asc) as rnk
+    from outbox o
+    inner join outbox_lock ol on o.partition_key = ol.partition_key
+    where o.process_after < now()
+        and o.processed_at is null
+        and not o.unordered
+        and o.attempts < $2
+        and o.dead_lettered_at is null
+        and ol.lock_id = $1
+)
+select o.*
+from outbox o
+inner join locked l on o.idempotent_key = l.idempotent_key
+where l.rnk = 1"#;
+
+        sqlx::query_as(sql_list)
+            .bind(lock_id)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .fetch_all(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list outboxes"))
+    }
+
+    #[instrument(skip_all)]
+    async fn resolve_shard(
+        &self,
+        app_state: &AppState,
+    ) -> Result<(i64, i64), OutboxPatternProcessorError> {
+        match app_state.worker_sharding {
+            None => Ok((0, 1)),
+            Some(WorkerSharding::Static { shard_index, shard_count }) => {
+                if shard_count == 0 || shard_index >= shard_count {
+                    return Err(OutboxPatternProcessorError::new(
+                        &format!("shard_index={shard_index}, shard_count={shard_count}"),
+                        "Invalid worker sharding",
+                    ));
+                }
+
+                Ok((shard_index as i64, shard_count as i64))
+            },
+            Some(WorkerSharding::Dynamic {
+                worker_id,
+                heartbeat_timeout_in_seconds,
+            }) => {
+                let workers = self.heartbeat_worker(worker_id, heartbeat_timeout_in_seconds).await?;
+                let shard_index = workers.iter().position(|it| *it == worker_id).unwrap_or(0);
+
+                Ok((shard_index as i64, workers.len().max(1) as i64))
+            },
+        }
+    }
+
+    #[instrument(skip_all)]
+    async fn heartbeat_worker(
+        &self,
+        worker_id: Uuid,
+        heartbeat_timeout_in_seconds: u64,
+    ) -> Result<Vec<Uuid>, OutboxPatternProcessorError> {
+        let sql_heartbeat = "insert into outbox_worker (worker_id, heartbeat_at) values ($1, now()) on conflict (worker_id) do update set heartbeat_at = now()";
+
+        sqlx::query(sql_heartbeat)
+            .bind(worker_id)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to heartbeat outbox worker"))?;
+
+        let sql_expire = "delete from outbox_worker where heartbeat_at < now() - ($1)::interval";
+
+        sqlx::query(sql_expire)
+            .bind(format!("{heartbeat_timeout_in_seconds} seconds"))
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to expire outbox workers"))?;
+
+        sqlx::query_scalar("select worker_id from outbox_worker order by worker_id")
+            .fetch_all(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list outbox workers"))
+    }
+
+    #[instrument(skip_all)]
+    async fn lock_partition_key(
+        &self,
+        app_state: &AppState,
+        lock_id: Uuid,
+        processing_until_incremente_interval: String,
+        shard_index: i64,
+        shard_count: i64,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql_lock = r#"with pending as (
+    select
+        o.partition_key,
+        min(o.process_after) as process_after,
+        max(o.priority) as priority,
+        max(coalesce(case when $8::text is null then o.tenant else o.headers ->> $8 end, '')) as tenant,
+        row_number() over (order by min(o.process_after)) as age_rank
+    from outbox o
+    left join outbox_lock ol on o.partition_key = ol.partition_key and ol.processed_at is null
+    where ol.partition_key is null
+        and o.processed_at is null
+        and not o.unordered
+        and o.process_after < now()
+        and o.attempts < $4
+        and o.dead_lettered_at is null
+        and mod(mod(hashtextextended(o.partition_key::text, 0), $5) + $5, $5) = $6
+    group by o.partition_key
+),
+ranked as (
+    select
+        p.*,
+        row_number() over (partition by p.tenant order by p.priority desc, p.process_after) as tenant_rank,
+        coalesce(w.weight, $12) as weight
+    from pending p
+    left join unnest($10::text[], $11::int[]) as w(tenant, weight) on w.tenant = p.tenant
+)
+insert into outbox_lock (partition_key, lock_id, processing_until)
+select r.partition_key, $1 as lock_id, now() + ($3)::interval as processing_until
+from ranked r
+where r.tenant_rank <= $9
+order by
+    case when r.age_rank <= $7 then 0 else 1 end,
+    (r.tenant_rank - 1) / r.weight,
+    r.priority desc,
+    r.process_after
+limit $2
+ON CONFLICT DO NOTHING"#;
+
+        let tenant_fairness = TenantFairnessParams::from_app_state(app_state);
+
+        sqlx::query(sql_lock)
+            .bind(lock_id)
+            .bind(app_state.outbox_query_limit.unwrap_or(50) as i32)
+            .bind(processing_until_incremente_interval)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .bind(shard_count)
+            .bind(shard_index)
+            .bind(priority_fair_share_limit(app_state))
+            .bind(tenant_fairness.header)
+            .bind(tenant_fairness.max_share_limit)
+            .bind(tenant_fairness.tenants)
+            .bind(tenant_fairness.weights)
+            .bind(tenant_fairness.default_weight)
+            .execute(&self.postgres_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to lock outboxes"))?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn lock_partition_key_with_advisory_lock(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        shard_index: i64,
+        shard_count: i64,
+    ) -> Result<Vec<Uuid>, OutboxPatternProcessorError> {
+        let sql_lock = r#"with pending as (
+    select
+        o.partition_key,
+        min(o.process_after) as process_after,
+        max(o.priority) as priority,
+        max(coalesce(case when $7::text is null then o.tenant else o.headers ->> $7 end, '')) as tenant,
+        row_number() over (order by min(o.process_after)) as age_rank
+    from outbox o
+    where o.processed_at is null
+        and not o.unordered
+        and o.process_after < now()
+        and o.attempts < $2
+        and o.dead_lettered_at is null
+        and mod(mod(hashtextextended(o.partition_key::text, 0), $4) + $4, $4) = $5
+    group by o.partition_key
+),
+ranked as (
+    select
+        p.*,
+        row_number() over (partition by p.tenant order by p.priority desc, p.process_after) as tenant_rank,
+        coalesce(w.weight, $11) as weight
+    from pending p
+    left join unnest($9::text[], $10::int[]) as w(tenant, weight) on w.tenant = p.tenant
+),
+candidates as materialized (
+    select r.partition_key
+    from ranked r
+    where r.tenant_rank <= $8
+    order by
+        case when r.age_rank <= $6 then 0 else 1 end,
+        (r.tenant_rank - 1) / r.weight,
+        r.priority desc,
+        r.process_after
+    limit $3
+)
+select c.partition_key
+from candidates c
+where pg_try_advisory_xact_lock(hashtextextended(c.partition_key::text, 0))
+limit $1"#;
+
+        let tenant_fairness = TenantFairnessParams::from_app_state(app_state);
+
+        sqlx::query_scalar(sql_lock)
+            .bind(app_state.outbox_query_limit.unwrap_or(50) as i32)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .bind((app_state.outbox_query_limit.unwrap_or(50) * ADVISORY_LOCK_CANDIDATES_FACTOR) as i32)
+            .bind(shard_count)
+            .bind(shard_index)
+            .bind(priority_fair_share_limit(app_state))
+            .bind(tenant_fairness.header)
+            .bind(tenant_fairness.max_share_limit)
+            .bind(tenant_fairness.tenants)
+            .bind(tenant_fairness.weights)
+            .bind(tenant_fairness.default_weight)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to lock outboxes"))
+    }
+
+    #[instrument(skip_all)]
+    async fn lock_partition_key_with_partition_table(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        shard_index: i64,
+        shard_count: i64,
+    ) -> Result<Vec<Uuid>, OutboxPatternProcessorError> {
+        let sql_lock = r#"with pending as (
+    select
+        o.partition_key,
+        min(o.process_after) as process_after,
+        max(o.priority) as priority,
+        max(coalesce(case when $6::text is null then o.tenant else o.headers ->> $6 end, '')) as tenant,
+        row_number() over (order by min(o.process_after)) as age_rank
+    from outbox o
+    where o.processed_at is null
+        and not o.unordered
+        and o.process_after < now()
+        and o.attempts < $2
+        and o.dead_lettered_at is null
+        and mod(mod(hashtextextended(o.partition_key::text, 0), $3) + $3, $3) = $4
+    group by o.partition_key
+),
+ranked as (
+    select
+        p.*,
+        row_number() over (partition by p.tenant order by p.priority desc, p.process_after) as tenant_rank,
+        coalesce(w.weight, $10) as weight
+    from pending p
+    left join unnest($8::text[], $9::int[]) as w(tenant, weight) on w.tenant = p.tenant
+)
+select p.partition_key
+from outbox_partition p
+inner join ranked r on r.partition_key = p.partition_key
+where r.tenant_rank <= $7
+order by
+    case when r.age_rank <= $5 then 0 else 1 end,
+    (r.tenant_rank - 1) / r.weight,
+    r.priority desc,
+    r.process_after
+limit $1
+for update of p skip locked"#;
+
+        let tenant_fairness = TenantFairnessParams::from_app_state(app_state);
+
+        sqlx::query_scalar(sql_lock)
+            .bind(app_state.outbox_query_limit.unwrap_or(50) as i32)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .bind(shard_count)
+            .bind(shard_index)
+            .bind(priority_fair_share_limit(app_state))
+            .bind(tenant_fairness.header)
+            .bind(tenant_fairness.max_share_limit)
+            .bind(tenant_fairness.tenants)
+            .bind(tenant_fairness.weights)
+            .bind(tenant_fairness.default_weight)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to lock outboxes"))
+    }
+
+    #[instrument(skip_all)]
+    async fn get_outboxes_ranked_from_partition_keys(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        partition_keys: &[Uuid],
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        let sql_list = r#"with locked as (
+    select
+        o.idempotent_key,
+        row_number() over (partition by o.partition_key order by o.process_after asc) as rnk
+    from outbox o
+    where o.partition_key = ANY($1)
+        and o.process_after < now()
+        and o.processed_at is null
+        and not o.unordered
+        and o.attempts < $2
+        and o.dead_lettered_at is null
+)
+select o.*
+from outbox o
+inner join locked l on o.idempotent_key = l.idempotent_key
+where l.rnk = 1
+for update of o"#;
+
+        sqlx::query_as(sql_list)
+            .bind(partition_keys)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list outboxes"))
+    }
+
+    #[instrument(skip_all)]
+    async fn clear_drained_partitions(
+        transaction: &mut Transaction<'_, Postgres>,
+        partition_keys: &[Uuid],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = r#"delete from outbox_partition p
+where p.partition_key = ANY($1)
+    and not exists (select 1 from outbox o where o.partition_key = p.partition_key and o.processed_at is null)"#;
+
+        sqlx::query(sql)
+            .bind(partition_keys)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to clear drained partitions"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub(crate) async fn mark_as_processed(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "update outbox set processed_at = now(), attempts = attempts + 1 where idempotent_key = ANY($1)";
+
+        let mut ids_to_update = outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>();
+        ids_to_update.push(Uuid::now_v7());
+
+        sqlx::query(sql)
+            .bind(ids_to_update)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to mark outboxes as processed"))?;
+
+        Self::unlock(app_state, transaction, outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub(crate) async fn delete_processed(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "delete from outbox where idempotent_key = ANY($1)";
+
+        let mut ids_to_delete = outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>();
+        ids_to_delete.push(Uuid::now_v7());
+
+        sqlx::query(sql)
+            .bind(ids_to_delete)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to delete processed outboxes"))?;
+
+        Self::unlock(app_state, transaction, outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub(crate) async fn increase_attempts(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        if let Some(delay) = app_state.delay_for_failure_attempt_in_seconds {
+            if delay > 0 {
+                let sql = "update outbox set process_after = $2 where (partition_key = ANY($1) and not unordered or idempotent_key = ANY($3)) and processed_at is null";
+
+                let (unordered_outboxes, ordered_outboxes): (Vec<&Outbox>, Vec<&Outbox>) = outboxes.iter().partition(|it| it.unordered);
+
+                sqlx::query(sql)
+                    .bind(ordered_outboxes.iter().map(|it| it.partition_key).collect::<Vec<Uuid>>())
+                    .bind(Utc::now() + Duration::from_secs(delay))
+                    .bind(unordered_outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>())
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to increase attempts"))?;
+            }
+        }
+
+        let sql = "update outbox set attempts = attempts + 1 where idempotent_key = ANY($1)";
+
+        sqlx::query(sql)
+            .bind(outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>())
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to increase attempts"))?;
+
+        Self::unlock(app_state, transaction, outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub(crate) async fn dead_letter(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "update outbox set dead_lettered_at = now(), attempts = attempts + 1 where idempotent_key = ANY($1)";
+
+        sqlx::query(sql)
+            .bind(outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>())
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to dead letter outboxes"))?;
+
+        Self::unlock(app_state, transaction, outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn unlock(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        if app_state.lock_strategy.unwrap_or_default() != LockStrategy::LockTable {
+            return Ok(());
+        }
+
+        let ordered_outboxes = outboxes.iter().filter(|it| !it.unordered).cloned().collect::<Vec<Outbox>>();
+
+        Self::unlock_by_partition_key(app_state, transaction, &ordered_outboxes).await?;
+        Self::unlock_expired_processes(app_state, transaction, &ordered_outboxes).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn unlock_expired_processes(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql_unlock_by_processing_until = if app_state.scheduled_clear_locked_partition.unwrap_or(false) {
+            "update outbox_lock set processed_at = now() where processing_until < now()"
+        } else {
+            "delete from outbox_lock where processing_until < now()"
+        };
+
+        let mut ids_to_delete = outboxes.iter().map(|it| it.partition_key).collect::<Vec<Uuid>>();
+        ids_to_delete.push(Uuid::now_v7());
+
+        sqlx::query(sql_unlock_by_processing_until)
+            .bind(ids_to_delete)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to unlock partition keys"))?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn unlock_by_partition_key(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Postgres>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql_unlock_by_partition_key = if app_state.scheduled_clear_locked_partition.unwrap_or(false) {
+            "update outbox_lock set processed_at = now() where partition_key = ANY($1) and processed_at is null"
+        } else {
+            "delete from outbox_lock where partition_key = ANY($1) and processed_at is null"
+        };
+
+        let mut ids_to_delete = outboxes.iter().map(|it| it.partition_key).collect::<Vec<Uuid>>();
+        ids_to_delete.push(Uuid::now_v7());
+
+        sqlx::query(sql_unlock_by_partition_key)
+            .bind(ids_to_delete)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to unlock partition keys"))?;
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub(crate) async fn clear_processed_locks(transaction: &mut Transaction<'_, Postgres>) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "delete from outbox_lock where processed_at is not null and processed_at < now()";
+
+        sqlx::query(sql)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to clear processed locks"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub(crate) async fn find_cleaner_schedule(transaction: &mut Transaction<'_, Postgres>) -> Result<Option<OutboxCleanerSchedule>, OutboxPatternProcessorError> {
+        let sql = "select * from outbox_cleaner_schedule limit 1 for update skip locked";
+
+        sqlx::query_as(sql)
+            .fetch_optional(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to update last cleaner execution"))
+    }
+
+    #[instrument(skip_all)]
+    pub(crate) async fn update_last_cleaner_execution(transaction: &mut Transaction<'_, Postgres>) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "update outbox_cleaner_schedule set last_execution = now()";
+
+        sqlx::query(sql)
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to update last cleaner execution"))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+use crate::outbox_destination::OutboxDestination;
+use sqlx::types::Json;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow, Clone, PartialEq)]
+pub struct RegisteredDestination {
+    pub name: String,
+    pub destination: Json<OutboxDestination>,
+}
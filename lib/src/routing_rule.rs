@@ -0,0 +1,67 @@
+use crate::outbox::Outbox;
+use crate::outbox_destination::OutboxDestination;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_json_path::JsonPath;
+use tracing::log::error;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RoutingCondition {
+    Header { name: String, value: String },
+    JsonPath { path: String },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RoutingRule {
+    pub condition: RoutingCondition,
+    pub destinations: Vec<OutboxDestination>,
+}
+
+impl RoutingRule {
+    pub fn header(
+        name: &str,
+        value: &str,
+        destinations: Vec<OutboxDestination>,
+    ) -> Self {
+        Self {
+            condition: RoutingCondition::Header {
+                name: name.to_string(),
+                value: value.to_string(),
+            },
+            destinations,
+        }
+    }
+
+    pub fn json_path(
+        path: &str,
+        destinations: Vec<OutboxDestination>,
+    ) -> Self {
+        Self {
+            condition: RoutingCondition::JsonPath { path: path.to_string() },
+            destinations,
+        }
+    }
+
+    pub fn matches(
+        &self,
+        outbox: &Outbox,
+    ) -> bool {
+        match &self.condition {
+            RoutingCondition::Header { name, value } => outbox
+                .headers
+                .as_ref()
+                .map(|headers| headers.0.iter().any(|(key, header_value)| key.eq_ignore_ascii_case(name) && header_value == value))
+                .unwrap_or(false),
+            RoutingCondition::JsonPath { path } => match JsonPath::parse(path) {
+                Ok(json_path) => serde_json::from_str::<Value>(&outbox.payload)
+                    .map(|payload| !json_path.query(&payload).is_empty())
+                    .unwrap_or(false),
+                Err(error) => {
+                    error!("Failed to parse routing json path {} cause {}", path, error);
+                    false
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::outbox::Outbox;
+use crate::outbox_destination::OutboxDestination;
+use crate::routing_rule::RoutingRule;
+
+#[derive(Clone, Default)]
+pub struct RoutingTable {
+    pub rules: Vec<RoutingRule>,
+}
+
+impl RoutingTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(
+        self,
+        rule: RoutingRule,
+    ) -> Self {
+        let mut rules = self.rules;
+        rules.push(rule);
+
+        Self { rules }
+    }
+
+    pub fn route(
+        &self,
+        outbox: &Outbox,
+    ) -> Vec<OutboxDestination> {
+        let mut destinations: Vec<OutboxDestination> = vec![];
+
+        for rule in self.rules.iter().filter(|rule| rule.matches(outbox)) {
+            for destination in &rule.destinations {
+                if !destinations.contains(destination) {
+                    destinations.push(destination.clone());
+                }
+            }
+        }
+
+        destinations
+    }
+}
@@ -0,0 +1,50 @@
+use crate::error::OutboxPatternProcessorError;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::path::PathBuf;
+
+#[async_trait]
+pub trait SchemaRegistry: Send + Sync {
+    async fn find_schema(
+        &self,
+        event_type: &str,
+    ) -> Result<Option<Value>, OutboxPatternProcessorError>;
+}
+
+pub struct LocalSchemaRegistry {
+    pub directory: PathBuf,
+}
+
+impl LocalSchemaRegistry {
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: PathBuf::from(directory),
+        }
+    }
+}
+
+#[async_trait]
+impl SchemaRegistry for LocalSchemaRegistry {
+    async fn find_schema(
+        &self,
+        event_type: &str,
+    ) -> Result<Option<Value>, OutboxPatternProcessorError> {
+        if event_type.contains('/') || event_type.contains('\\') || event_type == ".." {
+            return Err(OutboxPatternProcessorError::new(
+                &format!("Event type {event_type} is outside of {}", self.directory.display()),
+                "Failed to find local schema",
+            ));
+        }
+
+        match tokio::fs::read_to_string(self.directory.join(format!("{event_type}.json"))).await {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to parse local schema for event type {event_type}"))),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(OutboxPatternProcessorError::new(
+                &error.to_string(),
+                &format!("Failed to read local schema for event type {event_type}"),
+            )),
+        }
+    }
+}
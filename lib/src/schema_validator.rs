@@ -0,0 +1,118 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::outbox_event::EVENT_TYPE_HEADER;
+use crate::schema_registry::SchemaRegistry;
+use jsonschema::Validator;
+use serde_json::Value;
+use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const DEFAULT_CACHE_TTL_IN_SECONDS: u64 = 300;
+
+#[derive(Clone)]
+struct CachedValidator {
+    validator: Option<Arc<Validator>>,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct SchemaValidator {
+    pub registry: Arc<dyn SchemaRegistry>,
+    pub event_type_header: String,
+    cache_ttl_in_seconds: u64,
+    cache: Arc<Mutex<HashMap<String, CachedValidator>>>,
+}
+
+impl SchemaValidator {
+    pub fn new<R: SchemaRegistry + 'static>(registry: R) -> Self {
+        Self {
+            registry: Arc::new(registry),
+            event_type_header: EVENT_TYPE_HEADER.to_string(),
+            cache_ttl_in_seconds: DEFAULT_CACHE_TTL_IN_SECONDS,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn with_event_type_header(
+        self,
+        event_type_header: &str,
+    ) -> Self {
+        Self {
+            registry: self.registry,
+            event_type_header: event_type_header.to_string(),
+            cache_ttl_in_seconds: self.cache_ttl_in_seconds,
+            cache: self.cache,
+        }
+    }
+
+    pub fn with_cache_ttl_in_seconds(
+        self,
+        cache_ttl_in_seconds: u64,
+    ) -> Self {
+        Self {
+            registry: self.registry,
+            event_type_header: self.event_type_header,
+            cache_ttl_in_seconds,
+            cache: self.cache,
+        }
+    }
+
+    async fn find_validator(
+        &self,
+        event_type: &str,
+    ) -> Result<Option<Arc<Validator>>, OutboxPatternProcessorError> {
+        if let Some(cached) = self.cache.lock().await.get(event_type) {
+            if cached.expires_at > Utc::now() {
+                return Ok(cached.validator.clone());
+            }
+        }
+
+        let validator = match self.registry.find_schema(event_type).await? {
+            Some(schema) => Some(Arc::new(
+                jsonschema::validator_for(&schema).map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to compile json schema"))?,
+            )),
+            None => None,
+        };
+
+        let cached = CachedValidator {
+            validator: validator.clone(),
+            expires_at: Utc::now() + Duration::from_secs(self.cache_ttl_in_seconds),
+        };
+        self.cache.lock().await.insert(event_type.to_string(), cached);
+
+        Ok(validator)
+    }
+
+    pub async fn validate(
+        &self,
+        outbox: &Outbox,
+    ) -> Result<Vec<String>, OutboxPatternProcessorError> {
+        if outbox.is_binary() {
+            return Ok(vec![]);
+        }
+
+        let event_type = outbox.headers.as_ref().and_then(|headers| {
+            headers
+                .0
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(&self.event_type_header))
+                .map(|(_, value)| value.clone())
+        });
+
+        let validator = match event_type {
+            Some(event_type) => match self.find_validator(&event_type).await? {
+                Some(validator) => validator,
+                None => return Ok(vec![]),
+            },
+            None => return Ok(vec![]),
+        };
+
+        match serde_json::from_slice::<Value>(&outbox.body_bytes()) {
+            Ok(payload) => Ok(validator.iter_errors(&payload).map(|error| format!("{} at {}", error, error.instance_path)).collect()),
+            Err(error) => Ok(vec![format!("Payload is not a valid json: {error}")]),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::outbox_columns::OutboxColumns;
+use sea_orm::{ConnectionTrait, DbBackend, Statement, Value};
+
+pub struct SeaOrmOutboxRepository;
+
+impl SeaOrmOutboxRepository {
+    pub async fn insert<C: ConnectionTrait>(
+        db_conn: &C,
+        outbox: Outbox,
+    ) -> Result<Outbox, OutboxPatternProcessorError> {
+        let outboxes = Self::insert_all(db_conn, vec![outbox]).await?;
+        Ok(outboxes[0].clone())
+    }
+
+    pub async fn insert_all<C: ConnectionTrait>(
+        db_conn: &C,
+        outboxes: Vec<Outbox>,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        if outboxes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut values: Vec<Value> = vec![];
+        for outbox in &outboxes {
+            let row = OutboxColumns::from_outbox(outbox)?;
+            values.extend([
+                row.idempotent_key.into(),
+                row.partition_key.into(),
+                row.destinations.into(),
+                row.headers.into(),
+                row.payload.into(),
+                row.payload_binary.into(),
+                row.content_type.into(),
+                row.content_encoding.into(),
+                row.encryption.into(),
+                row.created_at.into(),
+                row.process_after.into(),
+                row.priority.into(),
+                row.tenant.into(),
+                row.unordered.into(),
+                row.expires_at.into(),
+                row.payload_json.into(),
+            ]);
+        }
+
+        let statement = Statement::from_sql_and_values(DbBackend::Postgres, OutboxColumns::insert_statement(outboxes.len()), values);
+
+        db_conn.execute(statement).await.map_err(|error| {
+            OutboxPatternProcessorError::new(
+                &error.to_string(),
+                &format!(
+                    "Failed to insert outboxes to partition_keys={}",
+                    outboxes.iter().map(|it| it.partition_key.to_string()).collect::<Vec<_>>().join(", ")
+                ),
+            )
+        })?;
+
+        Ok(outboxes)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::error::OutboxPatternProcessorError;
+use async_trait::async_trait;
+use std::env;
+use std::path::{Path, PathBuf};
+
+#[async_trait]
+pub trait SecretResolver: Send + Sync {
+    async fn resolve(
+        &self,
+        key: &str,
+    ) -> Result<Option<String>, OutboxPatternProcessorError>;
+}
+
+pub struct EnvironmentSecretResolver;
+
+#[async_trait]
+impl SecretResolver for EnvironmentSecretResolver {
+    async fn resolve(
+        &self,
+        key: &str,
+    ) -> Result<Option<String>, OutboxPatternProcessorError> {
+        Ok(env::var(key).ok())
+    }
+}
+
+pub struct FileSecretResolver {
+    pub directory: PathBuf,
+}
+
+impl FileSecretResolver {
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: PathBuf::from(directory),
+        }
+    }
+}
+
+#[async_trait]
+impl SecretResolver for FileSecretResolver {
+    async fn resolve(
+        &self,
+        key: &str,
+    ) -> Result<Option<String>, OutboxPatternProcessorError> {
+        let outside_directory = || OutboxPatternProcessorError::new(&format!("Secret key {key} is outside of {}", self.directory.display()), "Failed to resolve file secret");
+
+        if Path::new(key).is_absolute() || key.split('/').any(|segment| segment == "..") {
+            return Err(outside_directory());
+        }
+
+        let read_error = |error: std::io::Error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to read file secret {key}"));
+
+        let directory = tokio::fs::canonicalize(&self.directory).await.map_err(read_error)?;
+        let path = match tokio::fs::canonicalize(self.directory.join(key)).await {
+            Ok(path) => path,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(read_error(error)),
+        };
+
+        if !path.starts_with(&directory) {
+            return Err(outside_directory());
+        }
+
+        match tokio::fs::read_to_string(path).await {
+            Ok(content) => Ok(Some(content.trim_end_matches(['\r', '\n']).to_string())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(read_error(error)),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::secret_resolver::{EnvironmentSecretResolver, SecretResolver};
+use regex::{Captures, Regex};
+use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::log::error;
+
+const ENVIRONMENT_PREFIX: &str = "env";
+const DEFAULT_CACHE_TTL_IN_SECONDS: u64 = 300;
+
+#[derive(Clone)]
+struct CachedSecret {
+    value: Option<String>,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct SecretResolvers {
+    resolvers: HashMap<String, Arc<dyn SecretResolver>>,
+    cache_ttl_in_seconds: u64,
+    cache: Arc<Mutex<HashMap<String, CachedSecret>>>,
+    placeholder_regex: Regex,
+}
+
+impl Default for SecretResolvers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretResolvers {
+    pub fn new() -> Self {
+        let resolvers: HashMap<String, Arc<dyn SecretResolver>> = HashMap::from([(ENVIRONMENT_PREFIX.to_string(), Arc::new(EnvironmentSecretResolver) as Arc<dyn SecretResolver>)]);
+
+        Self {
+            resolvers,
+            cache_ttl_in_seconds: DEFAULT_CACHE_TTL_IN_SECONDS,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            placeholder_regex: Regex::new("\\{\\{([^{}]+)}}").expect("Failed to create secret placeholder regex"),
+        }
+    }
+
+    pub fn with_resolver(
+        self,
+        prefix: &str,
+        resolver: impl SecretResolver + 'static,
+    ) -> Self {
+        let mut resolvers = self.resolvers;
+        resolvers.insert(prefix.to_string(), Arc::new(resolver));
+
+        Self {
+            resolvers,
+            cache_ttl_in_seconds: self.cache_ttl_in_seconds,
+            cache: self.cache,
+            placeholder_regex: self.placeholder_regex,
+        }
+    }
+
+    pub fn with_cache_ttl_in_seconds(
+        self,
+        cache_ttl_in_seconds: u64,
+    ) -> Self {
+        Self {
+            resolvers: self.resolvers,
+            cache_ttl_in_seconds,
+            cache: self.cache,
+            placeholder_regex: self.placeholder_regex,
+        }
+    }
+
+    pub async fn resolve(
+        &self,
+        key: &str,
+    ) -> Option<String> {
+        if let Some(cached) = self.cache.lock().await.get(key) {
+            if cached.expires_at > Utc::now() {
+                return cached.value.clone();
+            }
+        }
+
+        let (resolver, resolver_key) = match key.split_once(':') {
+            Some((prefix, resolver_key)) if self.resolvers.contains_key(prefix) => (self.resolvers.get(prefix)?, resolver_key),
+            _ => (self.resolvers.get(ENVIRONMENT_PREFIX)?, key),
+        };
+
+        match resolver.resolve(resolver_key).await {
+            Ok(value) => {
+                let cached = CachedSecret {
+                    value: value.clone(),
+                    expires_at: Utc::now() + Duration::from_secs(self.cache_ttl_in_seconds),
+                };
+                self.cache.lock().await.insert(key.to_string(), cached);
+                value
+            },
+            Err(error) => {
+                error!("{} - Cause: {}", error.message.unwrap_or("Failed to resolve secret".to_string()), error.cause);
+                None
+            },
+        }
+    }
+
+    pub async fn resolve_placeholders(
+        &self,
+        value: &str,
+    ) -> String {
+        let mut resolved = HashMap::new();
+        for captures in self.placeholder_regex.captures_iter(value) {
+            if let Entry::Vacant(entry) = resolved.entry(captures[1].trim().to_string()) {
+                let secret = self.resolve(entry.key()).await;
+                entry.insert(secret);
+            }
+        }
+
+        if resolved.is_empty() {
+            return value.to_string();
+        }
+
+        self.placeholder_regex
+            .replace_all(value, |captures: &Captures| {
+                resolved.get(captures[1].trim()).cloned().flatten().unwrap_or(captures[0].to_string())
+            })
+            .to_string()
+    }
+
+    pub(crate) fn resolve_environment_placeholders(
+        &self,
+        value: &str,
+    ) -> String {
+        self.placeholder_regex
+            .replace_all(value, |captures: &Captures| {
+                let key = captures[1].trim();
+                let env_name = key.strip_prefix(&format!("{ENVIRONMENT_PREFIX}:")).unwrap_or(key);
+
+                if env_name.contains(':') {
+                    return captures[0].to_string();
+                }
+
+                env::var(env_name).unwrap_or(captures[0].to_string())
+            })
+            .to_string()
+    }
+}
@@ -0,0 +1,110 @@
+use crate::app_state::AppState;
+use crate::error::OutboxPatternProcessorError;
+use serde::{Deserialize, Serialize};
+use sqlx::types::chrono::{DateTime, Utc};
+use tracing::log::{info, warn};
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SingletonJobKind {
+    LockCleanup,
+    Retention { retention_in_seconds: u64 },
+    BacklogMetrics,
+    BacklogSnapshot,
+    StuckPartitionDetection { threshold_in_seconds: u64 },
+    VacuumAnalyzeHints { dead_tuple_ratio: f64 },
+}
+
+impl SingletonJobKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SingletonJobKind::LockCleanup => "lock_cleanup",
+            SingletonJobKind::Retention { .. } => "retention",
+            SingletonJobKind::BacklogMetrics => "backlog_metrics",
+            SingletonJobKind::BacklogSnapshot => "backlog_snapshot",
+            SingletonJobKind::StuckPartitionDetection { .. } => "stuck_partition_detection",
+            SingletonJobKind::VacuumAnalyzeHints { .. } => "vacuum_analyze_hints",
+        }
+    }
+
+    pub(crate) async fn run(
+        &self,
+        app_state: &AppState,
+    ) -> Result<String, OutboxPatternProcessorError> {
+        let outbox_failure_limit = app_state.outbox_failure_limit.unwrap_or(10);
+
+        match self {
+            SingletonJobKind::LockCleanup => {
+                let cleared = app_state.storage.purge_processed_locks().await?;
+
+                Ok(format!("cleared_locks={cleared}"))
+            },
+            SingletonJobKind::Retention { retention_in_seconds } => {
+                let deleted = app_state.storage.purge_processed_outboxes(*retention_in_seconds).await?;
+
+                Ok(format!("deleted_outboxes={deleted}"))
+            },
+            SingletonJobKind::BacklogMetrics => {
+                let backlog = app_state.storage.find_backlog(outbox_failure_limit).await?;
+                let oldest_pending_age_in_seconds = backlog.oldest_pending_at.map(|it| (Utc::now() - it).num_seconds().max(0)).unwrap_or(0);
+
+                let result = format!(
+                    "pending={}, dead_lettered={}, oldest_pending_age_in_seconds={}",
+                    backlog.pending, backlog.dead_lettered, oldest_pending_age_in_seconds
+                );
+                info!("Outbox backlog: {}", result);
+
+                Ok(result)
+            },
+            SingletonJobKind::BacklogSnapshot => {
+                let backlog = app_state.storage.find_backlog(outbox_failure_limit).await?;
+                app_state.storage.save_backlog_snapshot(&backlog).await?;
+
+                Ok(format!("pending={}, dead_lettered={}", backlog.pending, backlog.dead_lettered))
+            },
+            SingletonJobKind::VacuumAnalyzeHints { dead_tuple_ratio } => {
+                let hints = app_state.storage.find_vacuum_analyze_hints(*dead_tuple_ratio).await?;
+
+                for hint in &hints {
+                    warn!("Outbox maintenance hint: {}", hint);
+                }
+
+                Ok(format!("hints={}", hints.len()))
+            },
+            SingletonJobKind::StuckPartitionDetection { threshold_in_seconds } => {
+                let partition_keys = app_state.storage.find_stuck_partition_keys(outbox_failure_limit, *threshold_in_seconds).await?;
+
+                if !partition_keys.is_empty() {
+                    warn!(
+                        "Outbox partition keys stuck for more than {} seconds: {}",
+                        threshold_in_seconds,
+                        partition_keys.iter().map(|it| it.to_string()).collect::<Vec<_>>().join(", ")
+                    );
+                }
+
+                Ok(format!("stuck_partitions={}", partition_keys.len()))
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SingletonJob {
+    pub kind: SingletonJobKind,
+    pub interval_in_seconds: u64,
+}
+
+impl SingletonJob {
+    pub fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    pub(crate) fn is_due(
+        &self,
+        last_run_at: Option<DateTime<Utc>>,
+    ) -> bool {
+        last_run_at
+            .map(|last_run_at| (Utc::now() - last_run_at).num_seconds() >= self.interval_in_seconds as i64)
+            .unwrap_or(true)
+    }
+}
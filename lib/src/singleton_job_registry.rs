@@ -0,0 +1,61 @@
+use crate::singleton_job::{SingletonJob, SingletonJobKind};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct SingletonJobRegistry {
+    pub holder_id: Uuid,
+    pub lease_duration_in_seconds: u64,
+    pub jobs: Vec<SingletonJob>,
+    pub load_from_database: bool,
+}
+
+impl SingletonJobRegistry {
+    pub fn new(holder_id: Uuid) -> Self {
+        Self {
+            holder_id,
+            lease_duration_in_seconds: 30,
+            jobs: vec![],
+            load_from_database: false,
+        }
+    }
+
+    pub fn with_lease_duration_in_seconds(
+        self,
+        lease_duration_in_seconds: u64,
+    ) -> Self {
+        Self {
+            holder_id: self.holder_id,
+            lease_duration_in_seconds,
+            jobs: self.jobs,
+            load_from_database: self.load_from_database,
+        }
+    }
+
+    pub fn with_job(
+        self,
+        kind: SingletonJobKind,
+        interval_in_seconds: u64,
+    ) -> Self {
+        let mut jobs = self.jobs.into_iter().filter(|it| it.name() != kind.name()).collect::<Vec<_>>();
+        jobs.push(SingletonJob { kind, interval_in_seconds });
+
+        Self {
+            holder_id: self.holder_id,
+            lease_duration_in_seconds: self.lease_duration_in_seconds,
+            jobs,
+            load_from_database: self.load_from_database,
+        }
+    }
+
+    pub fn with_load_from_database(
+        self,
+        load_from_database: bool,
+    ) -> Self {
+        Self {
+            holder_id: self.holder_id,
+            lease_duration_in_seconds: self.lease_duration_in_seconds,
+            jobs: self.jobs,
+            load_from_database,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::outbox_columns::OutboxColumns;
+use crate::outbox_insert_options::OutboxInsertOptions;
+use sqlx::types::chrono::Utc;
+use sqlx::{Acquire, QueryBuilder, Sqlite, SqliteConnection};
+use std::future::Future;
+use std::pin::Pin;
+use tracing::instrument;
+use uuid::Uuid;
+
+type ConnectionFuture<'x, T> = Pin<Box<dyn Future<Output = Result<T, OutboxPatternProcessorError>> + Send + 'x>>;
+
+pub struct SqliteOutboxRepository;
+
+impl SqliteOutboxRepository {
+    #[instrument(skip_all)]
+    pub async fn insert<'c, A>(
+        db_conn: A,
+        outbox: Outbox,
+    ) -> Result<Outbox, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Sqlite> + Send + 'c,
+    {
+        let outboxes = Self::insert_all(db_conn, vec![outbox]).await?;
+        Ok(outboxes[0].clone())
+    }
+
+    #[instrument(skip_all)]
+    pub async fn insert_all<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Sqlite> + Send + 'c,
+    {
+        if outboxes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Self::with_connection(db_conn, "Failed to acquire connection to insert outboxes", move |conn| {
+            Box::pin(async move {
+                Self::insert_query_builder(&outboxes)?
+                    .build()
+                    .execute(&mut *conn)
+                    .await
+                    .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &Self::insert_error_message(&outboxes)))?;
+
+                Ok(outboxes)
+            })
+        })
+        .await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn insert_all_with_options<'c, A>(
+        db_conn: A,
+        outboxes: Vec<Outbox>,
+        options: &OutboxInsertOptions,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Sqlite> + Send + 'c,
+    {
+        let outboxes = options.prepare(outboxes).await?;
+
+        Self::insert_all(db_conn, outboxes).await
+    }
+
+    #[instrument(skip_all)]
+    pub async fn cancel<'c, A>(
+        db_conn: A,
+        idempotent_key: Uuid,
+    ) -> Result<bool, OutboxPatternProcessorError>
+    where
+        A: Acquire<'c, Database = Sqlite> + Send + 'c,
+    {
+        Self::with_connection(db_conn, "Failed to acquire connection to cancel outbox", move |conn| {
+            Box::pin(async move {
+                let sql = "delete from outbox where idempotent_key = ? and processed_at is null and (processing_until is null or processing_until <= ?)";
+
+                let result = sqlx::query(sql)
+                    .bind(idempotent_key)
+                    .bind(Utc::now())
+                    .execute(&mut *conn)
+                    .await
+                    .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to cancel outbox idempotent_key={idempotent_key}")))?;
+
+                Ok(result.rows_affected() > 0)
+            })
+        })
+        .await
+    }
+
+    fn with_connection<'c, A, T, F>(
+        db_conn: A,
+        acquire_error_message: &'static str,
+        run: F,
+    ) -> ConnectionFuture<'c, T>
+    where
+        A: Acquire<'c, Database = Sqlite> + Send + 'c,
+        F: for<'x> FnOnce(&'x mut SqliteConnection) -> ConnectionFuture<'x, T> + Send + 'c,
+    {
+        Box::pin(async move {
+            let mut conn = db_conn
+                .acquire()
+                .await
+                .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), acquire_error_message))?;
+
+            run(&mut conn).await
+        })
+    }
+
+    fn insert_query_builder(outboxes: &[Outbox]) -> Result<QueryBuilder<'_, Sqlite>, OutboxPatternProcessorError> {
+        let rows = outboxes.iter().map(OutboxColumns::from_outbox).collect::<Result<Vec<_>, _>>()?;
+
+        let mut query_builder = QueryBuilder::new(format!("INSERT INTO outbox ({})", OutboxColumns::names()));
+
+        query_builder.push_values(rows, |mut b, row: OutboxColumns| {
+            b.push_bind(row.idempotent_key)
+                .push_bind(row.partition_key)
+                .push_bind(row.destinations)
+                .push_bind(row.headers)
+                .push_bind(row.payload)
+                .push_bind(row.payload_binary)
+                .push_bind(row.content_type)
+                .push_bind(row.content_encoding)
+                .push_bind(row.encryption)
+                .push_bind(row.created_at)
+                .push_bind(row.process_after)
+                .push_bind(row.priority)
+                .push_bind(row.tenant)
+                .push_bind(row.unordered)
+                .push_bind(row.expires_at)
+                .push_bind(row.payload_json);
+        });
+
+        Ok(query_builder)
+    }
+
+    fn insert_error_message(outboxes: &[Outbox]) -> String {
+        format!(
+            "Failed to insert outboxes to partition_keys={}",
+            outboxes.iter().map(|it| it.partition_key.to_string()).collect::<Vec<_>>().join(", ")
+        )
+    }
+}
@@ -0,0 +1,491 @@
+use crate::app_state::AppState;
+use crate::error::OutboxPatternProcessorError;
+use crate::outbox::Outbox;
+use crate::outbox_backlog::OutboxBacklog;
+use crate::outbox_job::OutboxJob;
+use crate::outbox_storage_backend::{ensure_supported_options, priority_fair_share_limit, push_ids, OutboxStorageBackend, STUCK_PARTITION_KEYS_LIMIT};
+use crate::registered_destination::RegisteredDestination;
+use async_trait::async_trait;
+use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::{Pool, QueryBuilder, Sqlite, Transaction};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct SqliteOutboxStorage {
+    sqlite_pool: Pool<Sqlite>,
+    lease: Mutex<Vec<Uuid>>,
+}
+
+#[async_trait]
+impl OutboxStorageBackend for SqliteOutboxStorage {
+    #[instrument(skip_all, name = "lock_and_get_outboxes")]
+    async fn list(
+        &self,
+        app_state: &AppState,
+    ) -> Result<Vec<Outbox>, OutboxPatternProcessorError> {
+        ensure_supported_options(app_state, "sqlite")?;
+
+        let sql_claim = r#"update outbox set processing_until = $1
+where idempotent_key in (
+    select heads.idempotent_key
+    from (
+        select
+            ranked.idempotent_key,
+            ranked.process_after,
+            ranked.priority,
+            row_number() over (order by ranked.process_after) as age_rank
+        from (
+            select
+                o.idempotent_key,
+                o.process_after,
+                max(o.priority) over (partition by o.partition_key) as priority,
+                row_number() over (partition by o.partition_key order by o.process_after asc) as rnk
+            from outbox o
+            where o.processed_at is null
+                and o.process_after < $2
+                and o.attempts < $3
+                and o.dead_lettered_at is null
+                and not exists (
+                    select 1
+                    from outbox io
+                    where io.partition_key = o.partition_key
+                        and io.processed_at is null
+                        and io.processing_until > $2
+                )
+        ) ranked
+        where ranked.rnk = 1
+    ) heads
+    order by
+        case when heads.age_rank <= $5 then 0 else 1 end,
+        heads.priority desc,
+        heads.process_after
+    limit $4
+)
+returning *"#;
+
+        let now = Utc::now();
+
+        let outboxes: Vec<Outbox> = sqlx::query_as(sql_claim)
+            .bind(now + Duration::from_secs(app_state.max_in_flight_interval_in_seconds.unwrap_or(30)))
+            .bind(now)
+            .bind(app_state.outbox_failure_limit.unwrap_or(10) as i32)
+            .bind(app_state.outbox_query_limit.unwrap_or(50) as i32)
+            .bind(priority_fair_share_limit(app_state))
+            .fetch_all(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to lock outboxes"))?;
+
+        *self.lease.lock().await = outboxes.iter().map(|it| it.idempotent_key).collect();
+
+        Ok(outboxes)
+    }
+
+    #[instrument(skip_all)]
+    async fn renew_lease(
+        &self,
+        app_state: &AppState,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let idempotent_keys = self.lease.lock().await.clone();
+        if idempotent_keys.is_empty() {
+            return Ok(true);
+        }
+
+        let now = Utc::now();
+
+        let mut query_builder = QueryBuilder::new("update outbox set processing_until = ");
+        query_builder.push_bind(now + Duration::from_secs(app_state.max_in_flight_interval_in_seconds.unwrap_or(30)));
+        query_builder.push(" where processed_at is null and processing_until > ");
+        query_builder.push_bind(now);
+        query_builder.push(" and idempotent_key in ");
+        push_ids(&mut query_builder, &idempotent_keys);
+
+        let result = query_builder
+            .build()
+            .execute(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to renew partition keys lease"))?;
+
+        Ok(result.rows_affected() as usize == idempotent_keys.len())
+    }
+
+    #[instrument(skip_all)]
+    async fn complete(
+        &self,
+        app_state: &AppState,
+        successfully_outboxes: &[Outbox],
+        failure_outboxes: &[Outbox],
+        invalid_outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut transaction = self
+            .sqlite_pool
+            .begin()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to create transaction"))?;
+
+        if app_state.delete_after_process_successfully.unwrap_or(false) {
+            Self::delete_processed(&mut transaction, successfully_outboxes).await?;
+        } else {
+            Self::mark_as_processed(&mut transaction, successfully_outboxes).await?;
+        }
+
+        if !failure_outboxes.is_empty() {
+            Self::increase_attempts(app_state, &mut transaction, failure_outboxes).await?;
+        }
+
+        if !invalid_outboxes.is_empty() {
+            Self::dead_letter(&mut transaction, invalid_outboxes).await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to commit transaction"))
+    }
+
+    #[instrument(skip_all)]
+    async fn find_registered_destinations(&self) -> Result<Vec<RegisteredDestination>, OutboxPatternProcessorError> {
+        let sql = "select name, destination from outbox_destination";
+
+        sqlx::query_as(sql)
+            .fetch_all(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list registered destinations"))
+    }
+
+    async fn clear_processed_locked_partition_key(&self) -> Result<(), OutboxPatternProcessorError> {
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn try_acquire_leadership(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+        lease_duration_in_seconds: u64,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let sql = r#"insert into outbox_leader (name, holder_id, lease_until)
+values ($1, $2, $3)
+on conflict (name) do update set holder_id = excluded.holder_id, lease_until = excluded.lease_until
+where outbox_leader.holder_id = excluded.holder_id or outbox_leader.lease_until <= $4
+returning holder_id"#;
+
+        let now = Utc::now();
+
+        let leader: Option<Uuid> = sqlx::query_scalar(sql)
+            .bind(name)
+            .bind(holder_id)
+            .bind(now + Duration::from_secs(lease_duration_in_seconds))
+            .bind(now)
+            .fetch_optional(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to acquire leadership"))?;
+
+        Ok(leader.is_some())
+    }
+
+    #[instrument(skip_all)]
+    async fn release_leadership(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        sqlx::query("update outbox_leader set lease_until = $3 where name = $1 and holder_id = $2")
+            .bind(name)
+            .bind(holder_id)
+            .bind(Utc::now())
+            .execute(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to release leadership"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn find_leadership_last_run_at(
+        &self,
+        name: &str,
+    ) -> Result<Option<DateTime<Utc>>, OutboxPatternProcessorError> {
+        let last_run_at: Option<Option<DateTime<Utc>>> = sqlx::query_scalar("select last_run_at from outbox_leader where name = $1")
+            .bind(name)
+            .fetch_optional(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to find leadership last run name={name}")))?;
+
+        Ok(last_run_at.flatten())
+    }
+
+    #[instrument(skip_all)]
+    async fn record_leadership_run(
+        &self,
+        name: &str,
+        holder_id: Uuid,
+        last_run_at: DateTime<Utc>,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        sqlx::query("update outbox_leader set last_run_at = $3 where name = $1 and holder_id = $2")
+            .bind(name)
+            .bind(holder_id)
+            .bind(last_run_at)
+            .execute(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to record leadership run name={name}")))?;
+
+        Ok(())
+    }
+
+    async fn purge_processed_locks(&self) -> Result<u64, OutboxPatternProcessorError> {
+        Ok(0)
+    }
+
+    #[instrument(skip_all)]
+    async fn purge_processed_outboxes(
+        &self,
+        retention_in_seconds: u64,
+    ) -> Result<u64, OutboxPatternProcessorError> {
+        let sql = "delete from outbox where processed_at is not null and processed_at < $1";
+
+        let result = sqlx::query(sql)
+            .bind(Utc::now() - Duration::from_secs(retention_in_seconds))
+            .execute(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to delete processed outboxes"))?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip_all)]
+    async fn find_backlog(
+        &self,
+        outbox_failure_limit: u32,
+    ) -> Result<OutboxBacklog, OutboxPatternProcessorError> {
+        let sql = r#"select
+    coalesce(sum(attempts < $1 and dead_lettered_at is null), 0) as pending,
+    coalesce(sum(attempts >= $1 or dead_lettered_at is not null), 0) as dead_lettered,
+    min(case when attempts < $1 and dead_lettered_at is null then process_after end) as oldest_pending_at
+from outbox
+where processed_at is null"#;
+
+        sqlx::query_as(sql)
+            .bind(outbox_failure_limit as i32)
+            .fetch_one(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to find outbox backlog"))
+    }
+
+    #[instrument(skip_all)]
+    async fn find_stuck_partition_keys(
+        &self,
+        outbox_failure_limit: u32,
+        threshold_in_seconds: u64,
+    ) -> Result<Vec<Uuid>, OutboxPatternProcessorError> {
+        let sql = r#"select partition_key
+from outbox
+where processed_at is null
+    and attempts < $1
+    and dead_lettered_at is null
+group by partition_key
+having min(process_after) < $2
+order by min(process_after)
+limit $3"#;
+
+        sqlx::query_scalar(sql)
+            .bind(outbox_failure_limit as i32)
+            .bind(Utc::now() - Duration::from_secs(threshold_in_seconds))
+            .bind(STUCK_PARTITION_KEYS_LIMIT)
+            .fetch_all(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to find stuck partition keys"))
+    }
+
+    #[instrument(skip_all)]
+    async fn save_backlog_snapshot(
+        &self,
+        backlog: &OutboxBacklog,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "insert into outbox_backlog_snapshot (taken_at, pending, dead_lettered, oldest_pending_at) values ($1, $2, $3, $4)";
+
+        sqlx::query(sql)
+            .bind(Utc::now())
+            .bind(backlog.pending)
+            .bind(backlog.dead_lettered)
+            .bind(backlog.oldest_pending_at)
+            .execute(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to save outbox backlog snapshot"))?;
+
+        Ok(())
+    }
+
+    async fn find_vacuum_analyze_hints(
+        &self,
+        _dead_tuple_ratio: f64,
+    ) -> Result<Vec<String>, OutboxPatternProcessorError> {
+        Err(OutboxPatternProcessorError::new(
+            "VACUUM and ANALYZE hints are only available for Postgres",
+            "Failed to find outbox tables statistics",
+        ))
+    }
+
+    #[instrument(skip_all)]
+    async fn find_jobs(&self) -> Result<Vec<OutboxJob>, OutboxPatternProcessorError> {
+        sqlx::query_as("select * from outbox_job order by name")
+            .fetch_all(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to list outbox jobs"))
+    }
+
+    #[instrument(skip_all)]
+    async fn save_job(
+        &self,
+        job: &OutboxJob,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = r#"insert into outbox_job (name, kind, cron_expression, enabled)
+values ($1, $2, $3, $4)
+on conflict (name) do update set kind = excluded.kind, cron_expression = excluded.cron_expression, enabled = excluded.enabled"#;
+
+        sqlx::query(sql)
+            .bind(&job.name)
+            .bind(&job.kind)
+            .bind(&job.cron_expression)
+            .bind(job.enabled)
+            .execute(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to save outbox job name={}", job.name)))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn delete_job(
+        &self,
+        name: &str,
+    ) -> Result<bool, OutboxPatternProcessorError> {
+        let result = sqlx::query("delete from outbox_job where name = $1")
+            .bind(name)
+            .execute(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to delete outbox job name={name}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip_all)]
+    async fn record_job_run(
+        &self,
+        name: &str,
+        last_run_at: DateTime<Utc>,
+        last_result: &str,
+        last_duration_in_millis: i64,
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let sql = "update outbox_job set last_run_at = $2, last_result = $3, last_duration_in_millis = $4 where name = $1";
+
+        sqlx::query(sql)
+            .bind(name)
+            .bind(last_run_at)
+            .bind(last_result)
+            .bind(last_duration_in_millis)
+            .execute(&self.sqlite_pool)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), &format!("Failed to record outbox job run name={name}")))?;
+
+        Ok(())
+    }
+}
+
+impl SqliteOutboxStorage {
+    pub fn new(sqlite_pool: Pool<Sqlite>) -> Self {
+        Self {
+            sqlite_pool,
+            lease: Mutex::new(vec![]),
+        }
+    }
+
+    #[instrument(skip_all)]
+    async fn mark_as_processed(
+        transaction: &mut Transaction<'_, Sqlite>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut query_builder = QueryBuilder::new("update outbox set processed_at = ");
+        query_builder.push_bind(Utc::now());
+        query_builder.push(", attempts = attempts + 1, processing_until = null where idempotent_key in ");
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to mark outboxes as processed"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn delete_processed(
+        transaction: &mut Transaction<'_, Sqlite>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut query_builder = QueryBuilder::new("delete from outbox where idempotent_key in ");
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to delete processed outboxes"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn increase_attempts(
+        app_state: &AppState,
+        transaction: &mut Transaction<'_, Sqlite>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        if let Some(delay) = app_state.delay_for_failure_attempt_in_seconds {
+            if delay > 0 {
+                let mut query_builder = QueryBuilder::new("update outbox set process_after = ");
+                query_builder.push_bind(Utc::now() + Duration::from_secs(delay));
+                query_builder.push(" where processed_at is null and partition_key in ");
+                push_ids(&mut query_builder, &outboxes.iter().map(|it| it.partition_key).collect::<Vec<Uuid>>());
+
+                query_builder
+                    .build()
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to increase attempts"))?;
+            }
+        }
+
+        let mut query_builder = QueryBuilder::new("update outbox set attempts = attempts + 1, processing_until = null where idempotent_key in ");
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to increase attempts"))?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn dead_letter(
+        transaction: &mut Transaction<'_, Sqlite>,
+        outboxes: &[Outbox],
+    ) -> Result<(), OutboxPatternProcessorError> {
+        let mut query_builder = QueryBuilder::new("update outbox set dead_lettered_at = ");
+        query_builder.push_bind(Utc::now());
+        query_builder.push(", attempts = attempts + 1, processing_until = null where idempotent_key in ");
+        push_ids(&mut query_builder, &outboxes.iter().map(|it| it.idempotent_key).collect::<Vec<Uuid>>());
+
+        query_builder
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .map_err(|error| OutboxPatternProcessorError::new(&error.to_string(), "Failed to dead letter outboxes"))?;
+
+        Ok(())
+    }
+}
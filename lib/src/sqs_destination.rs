@@ -1,7 +1,13 @@
+use crate::cloud_events::CloudEventsMode;
+use crate::payload_compression::PayloadCompression;
+use crate::payload_transform::PayloadTransform;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
 #[derive(Deserialize, Serialize, Debug, FromRow, Clone, PartialEq)]
 pub struct SqsDestination {
     pub queue_url: String,
+    pub transforms: Option<Vec<PayloadTransform>>,
+    pub cloud_events: Option<CloudEventsMode>,
+    pub compression: Option<PayloadCompression>,
 }
@@ -48,22 +48,20 @@ impl SqsNotificationService {
 
                     let mut entry_builder = SendMessageBatchRequestEntry::builder()
                         .id(outbox.idempotent_key)
-                        .message_body(outbox.payload.clone())
+                        .message_body(outbox.encoded_payload())
                         .message_attributes("x-idempotent-key", idempotent_key_attribute_value);
 
-                    if let Some(headers) = outbox.headers.clone() {
-                        for (key, value) in headers.0 {
-                            let attribute_value_result = attribute_value(outbox, &value);
-                            if attribute_value_result.is_err() {
-                                notification_result.failed.push(outbox.clone());
-                                let error = attribute_value_result.expect_err("Failed to get expect attribute_value error");
-                                error!("{} - Cause: {}", error.message.unwrap_or("Failed to create attribute_value".to_string()), error.cause);
-                                break;
-                            }
-
-                            let attribute_value = attribute_value_result.expect("Failed to get expect attribute_value");
-                            entry_builder = entry_builder.message_attributes(key, attribute_value);
+                    for (key, value) in outbox.message_attributes() {
+                        let attribute_value_result = attribute_value(outbox, &value);
+                        if attribute_value_result.is_err() {
+                            notification_result.failed.push(outbox.clone());
+                            let error = attribute_value_result.expect_err("Failed to get expect attribute_value error");
+                            error!("{} - Cause: {}", error.message.unwrap_or("Failed to create attribute_value".to_string()), error.cause);
+                            break;
                         }
+
+                        let attribute_value = attribute_value_result.expect("Failed to get expect attribute_value");
+                        entry_builder = entry_builder.message_attributes(key, attribute_value);
                     }
 
                     let entry = entry_builder.build().map_err(|error| {
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TenantKey {
+    Column,
+    Header(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenantFairness {
+    pub tenant_key: TenantKey,
+    pub max_share_percentage: u32,
+    pub default_weight: u32,
+    pub weights: HashMap<String, u32>,
+}
+
+impl TenantFairness {
+    pub fn by_column() -> Self {
+        Self {
+            tenant_key: TenantKey::Column,
+            max_share_percentage: 100,
+            default_weight: 1,
+            weights: HashMap::new(),
+        }
+    }
+
+    pub fn by_header(header: &str) -> Self {
+        Self {
+            tenant_key: TenantKey::Header(header.to_string()),
+            ..Self::by_column()
+        }
+    }
+
+    pub fn with_max_share_percentage(
+        self,
+        max_share_percentage: u32,
+    ) -> Self {
+        Self { max_share_percentage, ..self }
+    }
+
+    pub fn with_default_weight(
+        self,
+        default_weight: u32,
+    ) -> Self {
+        Self { default_weight, ..self }
+    }
+
+    pub fn with_weight(
+        self,
+        tenant: &str,
+        weight: u32,
+    ) -> Self {
+        let mut weights = self.weights;
+        weights.insert(tenant.to_string(), weight);
+
+        Self { weights, ..self }
+    }
+
+    pub(crate) fn max_share_limit(
+        &self,
+        query_limit: u32,
+    ) -> i64 {
+        (query_limit as u64 * self.max_share_percentage.clamp(1, 100) as u64).div_ceil(100) as i64
+    }
+}
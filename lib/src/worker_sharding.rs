@@ -0,0 +1,36 @@
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WorkerSharding {
+    Static { shard_index: u32, shard_count: u32 },
+    Dynamic { worker_id: Uuid, heartbeat_timeout_in_seconds: u64 },
+}
+
+impl WorkerSharding {
+    pub fn fixed(
+        shard_index: u32,
+        shard_count: u32,
+    ) -> Self {
+        Self::Static { shard_index, shard_count }
+    }
+
+    pub fn dynamic(worker_id: Uuid) -> Self {
+        Self::Dynamic {
+            worker_id,
+            heartbeat_timeout_in_seconds: 30,
+        }
+    }
+
+    pub fn with_heartbeat_timeout_in_seconds(
+        self,
+        heartbeat_timeout_in_seconds: u64,
+    ) -> Self {
+        match self {
+            Self::Dynamic { worker_id, .. } => Self::Dynamic {
+                worker_id,
+                heartbeat_timeout_in_seconds,
+            },
+            _ => self,
+        }
+    }
+}
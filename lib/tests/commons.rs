@@ -3,12 +3,14 @@ use aws_sdk_sns::operation::create_topic::CreateTopicOutput;
 use aws_sdk_sqs::operation::create_queue::CreateQueueOutput;
 use outbox_pattern_processor::aws::{SnsClient, SqsClient};
 use outbox_pattern_processor::http_destination::HttpDestination;
+use outbox_pattern_processor::named_destination::NamedDestination;
 use outbox_pattern_processor::outbox::Outbox;
 use outbox_pattern_processor::outbox_destination::OutboxDestination;
 use outbox_pattern_processor::outbox_resources::OutboxProcessorResources;
 use outbox_pattern_processor::sns_destination::SnsDestination;
 use outbox_pattern_processor::sqs_destination::SqsDestination;
 use rand::Rng;
+use rcgen::{BasicConstraints, CertificateParams, ExtendedKeyUsagePurpose, IsCa, KeyPair};
 use serde_json::{json, Value};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use sqlx::types::chrono::{DateTime, Utc};
@@ -16,11 +18,19 @@ use sqlx::types::Json;
 use sqlx::{Pool, Postgres};
 use std::collections::HashMap;
 use std::env;
+use std::fs;
 use std::net::{SocketAddr, TcpListener};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 use test_context::AsyncTestContext;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_rustls::rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
 use uuid::Uuid;
-use wiremock::matchers::{body_json_string, header, method, path};
+use wiremock::matchers::{body_bytes, body_json_string, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[allow(dead_code)]
@@ -86,6 +96,65 @@ impl Infrastructure {
             .unwrap()
     }
 
+    #[cfg(feature = "sqlite")]
+    pub async fn init_sqlite_database() -> sqlx::Pool<sqlx::Sqlite> {
+        let sqlite_pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .connect_with(
+                sqlx::sqlite::SqliteConnectOptions::new()
+                    .filename(env::temp_dir().join(format!("outbox-{}.db", Uuid::now_v7())))
+                    .create_if_missing(true),
+            )
+            .await
+            .unwrap();
+
+        sqlx::raw_sql(include_str!("../../database/sqlite/1_outbox_pattern_table.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/2_outbox_destination_table.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/3_outbox_leader_table.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/4_outbox_job_table.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/5_outbox_priority.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/6_outbox_tenant.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/7_outbox_unordered.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/8_outbox_expires_at.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/9_outbox_dead_lettered_at.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/10_outbox_payload_json.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../database/sqlite/11_outbox_leader_last_run_at.sql"))
+            .execute(&sqlite_pool)
+            .await
+            .unwrap();
+
+        sqlite_pool
+    }
+
     async fn init_sqs(resources: &OutboxProcessorResources) -> CreateQueueOutput {
         resources.sqs_client.clone().unwrap().client.create_queue().queue_name("queue").send().await.unwrap()
     }
@@ -119,6 +188,10 @@ impl DefaultData {
                 url: format!("{}/success", ctx.gateway_uri),
                 headers: None,
                 method: None,
+                auth: None,
+                transforms: None,
+                cloud_events: None,
+                compression: None,
             })],
             None,
             None,
@@ -139,6 +212,10 @@ impl DefaultData {
                 url: format!("{}/success", ctx.gateway_uri),
                 headers: None,
                 method: None,
+                auth: None,
+                transforms: None,
+                cloud_events: None,
+                compression: None,
             })],
             None,
             None,
@@ -156,6 +233,10 @@ impl DefaultData {
                 url: format!("{}/failed", ctx.gateway_uri),
                 headers: None,
                 method: None,
+                auth: None,
+                transforms: None,
+                cloud_events: None,
+                compression: None,
             })],
             None,
             None,
@@ -176,6 +257,10 @@ impl DefaultData {
                 url: format!("{}/success", ctx.gateway_uri),
                 headers: None,
                 method: None,
+                auth: None,
+                transforms: None,
+                cloud_events: None,
+                compression: None,
             })],
             None,
             None,
@@ -196,6 +281,10 @@ impl DefaultData {
                 url: format!("{}/success", ctx.gateway_uri),
                 headers: None,
                 method: Some(method.to_string()),
+                auth: None,
+                transforms: None,
+                cloud_events: None,
+                compression: None,
             })],
             None,
             None,
@@ -217,6 +306,10 @@ impl DefaultData {
                 url: format!("{}/success", ctx.gateway_uri),
                 headers: Some(http_headers_map),
                 method: None,
+                auth: None,
+                transforms: None,
+                cloud_events: None,
+                compression: None,
             })],
             Some(outbox_headers_map),
             None,
@@ -225,12 +318,41 @@ impl DefaultData {
         .await
     }
 
+    pub async fn create_http_outbox_with_auth(
+        ctx: &mut TestContext,
+        auth: &str,
+    ) -> Outbox {
+        Self::create_outbox(
+            ctx,
+            None,
+            None,
+            vec![OutboxDestination::HttpDestination(HttpDestination {
+                url: format!("{}/success", ctx.gateway_uri),
+                headers: None,
+                method: None,
+                auth: Some(auth.to_string()),
+                transforms: None,
+                cloud_events: None,
+                compression: None,
+            })],
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+
     pub async fn create_default_sqs_outbox_success(ctx: &mut TestContext) -> Outbox {
         Self::create_outbox(
             ctx,
             None,
             None,
-            vec![OutboxDestination::SqsDestination(SqsDestination { queue_url: ctx.queue_url.clone() })],
+            vec![OutboxDestination::SqsDestination(SqsDestination {
+                queue_url: ctx.queue_url.clone(),
+                transforms: None,
+                cloud_events: None,
+                compression: None,
+            })],
             None,
             None,
             None,
@@ -245,6 +367,9 @@ impl DefaultData {
             None,
             vec![OutboxDestination::SqsDestination(SqsDestination {
                 queue_url: "https://invalid.queue.com".to_string(),
+                transforms: None,
+                cloud_events: None,
+                compression: None,
             })],
             None,
             None,
@@ -258,7 +383,12 @@ impl DefaultData {
             ctx,
             None,
             None,
-            vec![OutboxDestination::SnsDestination(SnsDestination { topic_arn: ctx.topic_arn.clone() })],
+            vec![OutboxDestination::SnsDestination(SnsDestination {
+                topic_arn: ctx.topic_arn.clone(),
+                transforms: None,
+                cloud_events: None,
+                compression: None,
+            })],
             None,
             None,
             None,
@@ -273,6 +403,9 @@ impl DefaultData {
             None,
             vec![OutboxDestination::SnsDestination(SnsDestination {
                 topic_arn: "invalid::arn".to_string(),
+                transforms: None,
+                cloud_events: None,
+                compression: None,
             })],
             None,
             None,
@@ -305,7 +438,7 @@ impl DefaultData {
             .bind(headers.map(|it| Some(Json(it))))
             .bind(payload.unwrap_or(json!({"foo":"bar"}).to_string()))
             .bind(process_after.unwrap_or(Utc::now()))
-            .fetch_one(&ctx.resources.postgres_pool)
+            .fetch_one(&ctx.postgres_pool)
             .await
             .unwrap()
     }
@@ -316,7 +449,7 @@ impl DefaultData {
         from outbox
         "#;
 
-        sqlx::query_as(sql).fetch_all(&ctx.resources.postgres_pool).await.unwrap()
+        sqlx::query_as(sql).fetch_all(&ctx.postgres_pool).await.unwrap()
     }
 
     pub async fn find_all_outboxes_processed(ctx: &mut TestContext) -> Vec<Outbox> {
@@ -326,7 +459,32 @@ impl DefaultData {
         where processed_at is not null
         "#;
 
-        sqlx::query_as(sql).fetch_all(&ctx.resources.postgres_pool).await.unwrap()
+        sqlx::query_as(sql).fetch_all(&ctx.postgres_pool).await.unwrap()
+    }
+
+    pub async fn create_named_outbox(
+        ctx: &mut TestContext,
+        name: &str,
+    ) -> Outbox {
+        Self::create_outbox(
+            ctx,
+            None,
+            None,
+            vec![OutboxDestination::NamedDestination(NamedDestination { name: name.to_string() })],
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+
+    pub async fn register_destination(
+        ctx: &mut TestContext,
+        name: &str,
+        destination: OutboxDestination,
+    ) {
+        let sql = "insert into outbox_destination (name, destination) values ($1, $2)";
+        let _ = sqlx::query(sql).bind(name).bind(Json(destination)).execute(&ctx.postgres_pool).await;
     }
 
     pub async fn create_lock(
@@ -351,7 +509,7 @@ impl DefaultData {
     ) {
         let last_execution = Utc::now() - Duration::from_secs(2);
         let sql = "insert into outbox_cleaner_schedule (cron_expression, last_execution) values ($1, $2)";
-        let _ = sqlx::query(&sql).bind(cron).bind(last_execution).execute(&ctx.postgres_pool).await;
+        let _ = sqlx::query(sql).bind(cron).bind(last_execution).execute(&ctx.postgres_pool).await;
     }
 
     pub async fn count_locks(ctx: &mut TestContext) -> i64 {
@@ -399,9 +557,34 @@ impl DefaultData {
     }
 
     pub async fn clear(ctx: &mut TestContext) {
-        let _ = sqlx::query("delete from outbox").execute(&ctx.resources.postgres_pool).await;
-        let _ = sqlx::query("delete from outbox_lock").execute(&ctx.resources.postgres_pool).await;
-        let _ = sqlx::query("delete from outbox_cleaner_schedule").execute(&ctx.resources.postgres_pool).await;
+        let _ = sqlx::query("delete from outbox").execute(&ctx.postgres_pool).await;
+        let _ = sqlx::query("delete from outbox_lock").execute(&ctx.postgres_pool).await;
+        let _ = sqlx::query("delete from outbox_cleaner_schedule").execute(&ctx.postgres_pool).await;
+        let _ = sqlx::query("delete from outbox_destination").execute(&ctx.postgres_pool).await;
+        let partition_table: Option<String> = sqlx::query_scalar("select to_regclass('outbox_partition')::text")
+            .fetch_one(&ctx.postgres_pool)
+            .await
+            .unwrap();
+        if partition_table.is_some() {
+            sqlx::query("delete from outbox_partition").execute(&ctx.postgres_pool).await.unwrap();
+        }
+        let _ = sqlx::query("delete from outbox_worker").execute(&ctx.postgres_pool).await;
+        let _ = sqlx::query("delete from outbox_leader").execute(&ctx.postgres_pool).await;
+        let _ = sqlx::query("delete from outbox_job").execute(&ctx.postgres_pool).await;
+        let _ = sqlx::query("delete from outbox_backlog_snapshot").execute(&ctx.postgres_pool).await;
+    }
+
+    pub async fn count_pending_partitions_in_shard(
+        ctx: &mut TestContext,
+        shard_index: i64,
+        shard_count: i64,
+    ) -> i64 {
+        sqlx::query_scalar("select count(distinct partition_key) from outbox where processed_at is null and mod(mod(hashtextextended(partition_key::text, 0), $2) + $2, $2) = $1")
+            .bind(shard_index)
+            .bind(shard_count)
+            .fetch_one(&ctx.postgres_pool)
+            .await
+            .unwrap()
     }
 }
 
@@ -437,6 +620,117 @@ impl HttpGatewayMock {
         Self::mock(ctx, outbox, "POST", None, Some(headers_map)).await;
     }
 
+    pub async fn mock_oauth2_token(
+        ctx: &mut TestContext,
+        access_token: &str,
+    ) {
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"access_token": access_token, "token_type": "Bearer", "expires_in": 3600})))
+            .mount(&ctx.mock_server)
+            .await;
+    }
+
+    pub async fn mock_path_with_payload(
+        ctx: &mut TestContext,
+        outbox: &Outbox,
+        request_path: &str,
+        payload: Value,
+    ) {
+        Mock::given(method("POST"))
+            .and(body_json_string(payload.to_string()))
+            .and(header("x-idempotent-key", outbox.idempotent_key.to_string()))
+            .and(path(request_path))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&ctx.mock_server)
+            .await;
+    }
+
+    pub async fn mock_binary(
+        ctx: &mut TestContext,
+        outbox: &Outbox,
+        content_type: &str,
+    ) {
+        Mock::given(method("POST"))
+            .and(body_bytes(outbox.payload_binary.clone().unwrap_or_default()))
+            .and(header("content-type", content_type))
+            .and(header("x-idempotent-key", outbox.idempotent_key.to_string()))
+            .and(path("/success"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&ctx.mock_server)
+            .await;
+    }
+
+    pub async fn mock_compressed(
+        ctx: &mut TestContext,
+        outbox: &Outbox,
+        content_encoding: &str,
+        body: Vec<u8>,
+    ) {
+        Mock::given(method("POST"))
+            .and(body_bytes(body))
+            .and(header("content-encoding", content_encoding))
+            .and(header("x-idempotent-key", outbox.idempotent_key.to_string()))
+            .and(path("/success"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&ctx.mock_server)
+            .await;
+    }
+
+    pub async fn mock_with_delay(
+        ctx: &mut TestContext,
+        outbox: &Outbox,
+        delay: Duration,
+    ) {
+        Mock::given(method("POST"))
+            .and(header("x-idempotent-key", outbox.idempotent_key.to_string()))
+            .and(path("/success"))
+            .respond_with(ResponseTemplate::new(200).set_delay(delay))
+            .mount(&ctx.mock_server)
+            .await;
+    }
+
+    pub async fn received_header_values(
+        ctx: &mut TestContext,
+        outbox: &Outbox,
+        header_name: &str,
+    ) -> Vec<String> {
+        ctx.mock_server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| {
+                request
+                    .headers
+                    .get("x-idempotent-key")
+                    .map(|value| value.to_str().unwrap_or_default() == outbox.idempotent_key.to_string())
+                    .unwrap_or(false)
+            })
+            .flat_map(|request| {
+                request
+                    .headers
+                    .get_all(header_name)
+                    .iter()
+                    .map(|value| value.to_str().unwrap_or_default().to_string())
+                    .collect::<Vec<String>>()
+            })
+            .collect()
+    }
+
+    pub async fn count_requests(
+        ctx: &mut TestContext,
+        request_path: &str,
+    ) -> usize {
+        ctx.mock_server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path() == request_path)
+            .count()
+    }
+
     async fn mock(
         ctx: &mut TestContext,
         outbox: &Outbox,
@@ -470,3 +764,115 @@ impl HttpGatewayMock {
             .await;
     }
 }
+
+#[allow(dead_code)]
+pub struct MutualTlsServer {
+    pub uri: String,
+    pub cert_path: String,
+    pub key_path: String,
+    pub ca_cert_path: String,
+    requests: Arc<AtomicUsize>,
+}
+
+#[allow(dead_code)]
+impl MutualTlsServer {
+    pub async fn start() -> Self {
+        let directory = env::temp_dir().join(Uuid::now_v7().to_string());
+        fs::create_dir_all(&directory).unwrap();
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(vec![]).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+
+        let server_key = KeyPair::generate().unwrap();
+        let mut server_params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        let server_cert = server_params.signed_by(&server_key, &ca_cert, &ca_key).unwrap();
+
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = CertificateParams::new(vec![]).unwrap();
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client_cert = client_params.signed_by(&client_key, &ca_cert, &ca_key).unwrap();
+
+        let cert_path = directory.join("client.crt");
+        let key_path = directory.join("client.key");
+        let ca_cert_path = directory.join("ca.crt");
+        fs::write(&cert_path, client_cert.pem()).unwrap();
+        fs::write(&key_path, client_key.serialize_pem()).unwrap();
+        fs::write(&ca_cert_path, ca_cert.pem()).unwrap();
+
+        let mut client_roots = RootCertStore::empty();
+        client_roots.add(ca_cert.der().clone()).unwrap();
+
+        let provider = Arc::new(tokio_rustls::rustls::crypto::ring::default_provider());
+        let client_verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(client_roots), provider.clone()).build().unwrap();
+        let server_config = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_client_cert_verifier(client_verifier)
+            .with_single_cert(vec![server_cert.der().clone()], PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(server_key.serialize_der())))
+            .unwrap();
+
+        let acceptor = TlsAcceptor::from(Arc::new(server_config));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let server_requests = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                let requests = server_requests.clone();
+
+                tokio::spawn(async move {
+                    if let Ok(mut stream) = acceptor.accept(stream).await {
+                        if Self::read_request(&mut stream).await {
+                            requests.fetch_add(1, Ordering::SeqCst);
+                            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n").await;
+                            let _ = stream.shutdown().await;
+                        }
+                    }
+                });
+            }
+        });
+
+        Self {
+            uri: format!("https://localhost:{port}"),
+            cert_path: cert_path.to_str().unwrap().to_string(),
+            key_path: key_path.to_str().unwrap().to_string(),
+            ca_cert_path: ca_cert_path.to_str().unwrap().to_string(),
+            requests,
+        }
+    }
+
+    pub fn count_requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    async fn read_request<S: AsyncReadExt + Unpin>(stream: &mut S) -> bool {
+        let mut request = vec![];
+        let mut buffer = [0; 1024];
+
+        loop {
+            if let Some(headers_end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                let headers = String::from_utf8_lossy(&request[..headers_end]).to_lowercase();
+                let content_length = headers
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .and_then(|value| value.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+
+                if request.len() >= headers_end + 4 + content_length {
+                    return true;
+                }
+            }
+
+            match stream.read(&mut buffer).await {
+                Ok(0) | Err(_) => return false,
+                Ok(read) => request.extend_from_slice(&buffer[..read]),
+            }
+        }
+    }
+}
@@ -4,8 +4,11 @@ use outbox_pattern_processor::environment::Environment;
 use outbox_pattern_processor::outbox_processor::OutboxProcessor;
 use outbox_pattern_processor::outbox_resources::OutboxProcessorResources;
 use outbox_pattern_processor::shutdown::Shutdown;
+use outbox_pattern_processor::singleton_job_registry::SingletonJobRegistry;
 use outbox_pattern_processor_worker::infra::database::Database;
 use outbox_pattern_processor_worker::routes::Routes;
+use outbox_pattern_processor_worker::state::AppState;
+use sqlx::types::Uuid;
 use sqlx::{Pool, Postgres};
 use std::env;
 use std::net::SocketAddr;
@@ -39,9 +42,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let sqs_client = SqsClient::new(&aws_config).await;
     let sns_client = SnsClient::new(&aws_config).await;
 
-    tokio::spawn(init_http_server(wait_group.add(1)));
+    let app_state = AppState {
+        postgres_pool: postgres_pool.clone(),
+        sqs_client: sqs_client.clone(),
+        sns_client: sns_client.clone(),
+    };
+
+    tokio::spawn(init_http_server(app_state, wait_group.add(1)));
     tokio::spawn(init_outbox(postgres_pool.clone(), sqs_client.clone(), sns_client.clone(), wait_group.add(1)));
-    tokio::spawn(init_outbox_lock_cleaner(postgres_pool, sqs_client, sns_client, wait_group.add(1)));
+    if Environment::boolean("OUTBOX_JOBS_ENABLED", false) {
+        tokio::spawn(init_outbox_jobs(postgres_pool, sqs_client, sns_client, wait_group.add(1)));
+    } else {
+        tokio::spawn(init_outbox_lock_cleaner(postgres_pool, sqs_client, sns_client, wait_group.add(1)));
+    }
 
     wait_group.wait();
 
@@ -50,9 +63,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-async fn init_http_server(wait_group: WaitGroup) {
+async fn init_http_server(
+    app_state: AppState,
+    wait_group: WaitGroup,
+) {
     info!("Starting http server...");
-    let routes = Routes::routes().await;
+    let routes = Routes::routes(&app_state).await;
 
     let addr = SocketAddr::from(([0, 0, 0, 0], 9095));
 
@@ -97,3 +113,20 @@ async fn init_outbox_lock_cleaner(
 
     wait_group.done();
 }
+
+async fn init_outbox_jobs(
+    postgres_pool: Pool<Postgres>,
+    sqs_client: SqsClient,
+    sns_client: SnsClient,
+    wait_group: WaitGroup,
+) {
+    let outbox_processor_resources = OutboxProcessorResources::new(postgres_pool, Some(sqs_client), Some(sns_client))
+        .with_singleton_jobs(SingletonJobRegistry::new(Uuid::now_v7()).with_load_from_database(true));
+
+    let _ = OutboxProcessor::new(outbox_processor_resources)
+        .with_graceful_shutdown(Shutdown::signal("Stopping outbox jobs..."))
+        .init_singleton_jobs()
+        .await;
+
+    wait_group.done();
+}
@@ -0,0 +1,281 @@
+use crate::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use outbox_pattern_processor::error::OutboxPatternProcessorError;
+use outbox_pattern_processor::outbox_job::OutboxJob;
+use outbox_pattern_processor::outbox_job_repository::OutboxJobRepository;
+use outbox_pattern_processor::singleton_job::SingletonJobKind;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+pub struct AdminRoutes;
+
+impl AdminRoutes {
+    pub fn routes(app_state: &AppState) -> Router {
+        Router::new()
+            .route("/jobs", get(list_jobs_handler))
+            .route("/jobs/:name", get(get_job_handler).put(save_job_handler).delete(delete_job_handler))
+            .route("/jobs/:name/enable", post(enable_job_handler))
+            .route("/jobs/:name/disable", post(disable_job_handler))
+            .with_state(app_state.clone())
+    }
+}
+
+#[derive(Deserialize)]
+struct JobRequest {
+    kind: SingletonJobKind,
+    cron_expression: String,
+    enabled: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct JobResponse {
+    name: String,
+    kind: SingletonJobKind,
+    cron_expression: String,
+    enabled: bool,
+    last_run_at: Option<String>,
+    last_result: Option<String>,
+    last_duration_in_millis: Option<i64>,
+}
+
+impl From<OutboxJob> for JobResponse {
+    fn from(job: OutboxJob) -> Self {
+        Self {
+            name: job.name,
+            kind: job.kind.0,
+            cron_expression: job.cron_expression,
+            enabled: job.enabled,
+            last_run_at: job.last_run_at.map(|it| it.to_rfc3339()),
+            last_result: job.last_result,
+            last_duration_in_millis: job.last_duration_in_millis,
+        }
+    }
+}
+
+async fn list_jobs_handler(State(app_state): State<AppState>) -> Result<Json<Vec<JobResponse>>, (StatusCode, String)> {
+    info!("GET /admin/jobs");
+
+    let jobs = repository(&app_state).find_all().await.map_err(internal_error)?;
+
+    Ok(Json(jobs.into_iter().map(JobResponse::from).collect()))
+}
+
+async fn get_job_handler(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<JobResponse>, (StatusCode, String)> {
+    info!("GET /admin/jobs/{name}");
+
+    match repository(&app_state).find_by_name(&name).await.map_err(internal_error)? {
+        None => Err(not_found(&name)),
+        Some(job) => Ok(Json(JobResponse::from(job))),
+    }
+}
+
+async fn save_job_handler(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<JobRequest>,
+) -> Result<Json<JobResponse>, (StatusCode, String)> {
+    info!("PUT /admin/jobs/{name}");
+
+    let job = OutboxJob::new(&name, request.kind, &request.cron_expression).with_enabled(request.enabled.unwrap_or(true));
+    job.validate().map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))?;
+
+    let repository = repository(&app_state);
+    repository.save(&job).await.map_err(internal_error)?;
+
+    match repository.find_by_name(&name).await.map_err(internal_error)? {
+        None => Err(not_found(&name)),
+        Some(job) => Ok(Json(JobResponse::from(job))),
+    }
+}
+
+async fn enable_job_handler(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<JobResponse>, (StatusCode, String)> {
+    info!("POST /admin/jobs/{name}/enable");
+
+    set_enabled(&app_state, &name, true).await
+}
+
+async fn disable_job_handler(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<JobResponse>, (StatusCode, String)> {
+    info!("POST /admin/jobs/{name}/disable");
+
+    set_enabled(&app_state, &name, false).await
+}
+
+async fn delete_job_handler(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    info!("DELETE /admin/jobs/{name}");
+
+    if repository(&app_state).delete(&name).await.map_err(internal_error)? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(not_found(&name))
+    }
+}
+
+async fn set_enabled(
+    app_state: &AppState,
+    name: &str,
+    enabled: bool,
+) -> Result<Json<JobResponse>, (StatusCode, String)> {
+    match repository(app_state).set_enabled(name, enabled).await.map_err(internal_error)? {
+        None => Err(not_found(name)),
+        Some(job) => Ok(Json(JobResponse::from(job))),
+    }
+}
+
+fn repository(app_state: &AppState) -> OutboxJobRepository {
+    OutboxJobRepository::new(app_state.postgres_pool.clone())
+}
+
+fn not_found(name: &str) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, format!("Outbox job name={name} not found"))
+}
+
+fn internal_error(error: OutboxPatternProcessorError) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::controller::admin::routes::AdminRoutes;
+    use crate::state::AppState;
+    use axum::body::Body;
+    use axum::http::{Method, Request, StatusCode};
+    use axum::Router;
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use sqlx::types::Uuid;
+    use tower::ServiceExt;
+
+    async fn send(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Option<Value>) {
+        let request = Request::builder().method(method).uri(uri).header("Content-Type", "application/json");
+        let request = match body {
+            None => request.body(Body::empty()),
+            Some(body) => request.body(Body::from(body.to_string())),
+        }
+        .unwrap();
+
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, serde_json::from_slice(&bytes).ok())
+    }
+
+    #[tokio::test]
+    async fn should_create_get_list_enable_disable_and_delete_job() {
+        let app_state = AppState::new().await.unwrap();
+        let router = AdminRoutes::routes(&app_state);
+
+        let name = format!("retention-{}", Uuid::now_v7());
+        let uri = format!("/jobs/{name}");
+
+        let (status, job) = send(
+            &router,
+            Method::PUT,
+            &uri,
+            Some(json!({"kind": {"kind": "retention", "retention_in_seconds": 604800}, "cron_expression": "0 0 * * * *", "enabled": false})),
+        )
+        .await;
+        assert_eq!(StatusCode::OK, status);
+        let job = job.unwrap();
+        assert_eq!(name, job["name"]);
+        assert_eq!(json!({"kind": "retention", "retention_in_seconds": 604800}), job["kind"]);
+        assert_eq!("0 0 * * * *", job["cron_expression"]);
+        assert_eq!(false, job["enabled"]);
+        assert!(job["last_run_at"].is_null());
+
+        let (status, job) = send(&router, Method::GET, &uri, None).await;
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!(name, job.unwrap()["name"]);
+
+        let (status, jobs) = send(&router, Method::GET, "/jobs", None).await;
+        assert_eq!(StatusCode::OK, status);
+        assert!(jobs.unwrap().as_array().unwrap().iter().any(|job| job["name"] == name.as_str()));
+
+        let (status, job) = send(&router, Method::POST, &format!("{uri}/enable"), None).await;
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!(true, job.unwrap()["enabled"]);
+
+        let (status, job) = send(&router, Method::POST, &format!("{uri}/disable"), None).await;
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!(false, job.unwrap()["enabled"]);
+
+        let (status, job) = send(
+            &router,
+            Method::PUT,
+            &uri,
+            Some(json!({"kind": {"kind": "lock_cleanup"}, "cron_expression": "0 */5 * * * *"})),
+        )
+        .await;
+        assert_eq!(StatusCode::OK, status);
+        let job = job.unwrap();
+        assert_eq!(json!({"kind": "lock_cleanup"}), job["kind"]);
+        assert_eq!("0 */5 * * * *", job["cron_expression"]);
+        assert_eq!(true, job["enabled"]);
+
+        let (status, _) = send(&router, Method::DELETE, &uri, None).await;
+        assert_eq!(StatusCode::NO_CONTENT, status);
+
+        let (status, _) = send(&router, Method::GET, &uri, None).await;
+        assert_eq!(StatusCode::NOT_FOUND, status);
+    }
+
+    #[tokio::test]
+    async fn should_return_not_found_for_unknown_job() {
+        let app_state = AppState::new().await.unwrap();
+        let router = AdminRoutes::routes(&app_state);
+
+        let uri = format!("/jobs/unknown-{}", Uuid::now_v7());
+
+        let (status, _) = send(&router, Method::GET, &uri, None).await;
+        assert_eq!(StatusCode::NOT_FOUND, status);
+
+        let (status, _) = send(&router, Method::POST, &format!("{uri}/enable"), None).await;
+        assert_eq!(StatusCode::NOT_FOUND, status);
+
+        let (status, _) = send(&router, Method::POST, &format!("{uri}/disable"), None).await;
+        assert_eq!(StatusCode::NOT_FOUND, status);
+
+        let (status, _) = send(&router, Method::DELETE, &uri, None).await;
+        assert_eq!(StatusCode::NOT_FOUND, status);
+    }
+
+    #[tokio::test]
+    async fn should_reject_job_with_invalid_cron_expression() {
+        let app_state = AppState::new().await.unwrap();
+        let router = AdminRoutes::routes(&app_state);
+
+        let uri = format!("/jobs/invalid-{}", Uuid::now_v7());
+
+        let (status, _) = send(
+            &router,
+            Method::PUT,
+            &uri,
+            Some(json!({"kind": {"kind": "backlog_metrics"}, "cron_expression": "not a cron"})),
+        )
+        .await;
+        assert_eq!(StatusCode::BAD_REQUEST, status);
+
+        let (status, _) = send(&router, Method::GET, &uri, None).await;
+        assert_eq!(StatusCode::NOT_FOUND, status);
+    }
+}
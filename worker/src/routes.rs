@@ -1,10 +1,23 @@
+use crate::controller::admin::routes::AdminRoutes;
 use crate::controller::health::routes::HealthRoutes;
+use crate::state::AppState;
 use axum::Router;
+use outbox_pattern_processor::environment::Environment;
+use tower_http::validate_request::ValidateRequestHeaderLayer;
+use tracing::info;
 
 pub struct Routes;
 
 impl Routes {
-    pub async fn routes() -> Router {
-        Router::new().nest("/health", HealthRoutes::routes())
+    pub async fn routes(app_state: &AppState) -> Router {
+        let routes = Router::new().nest("/health", HealthRoutes::routes());
+
+        let admin_api_token = Environment::string("ADMIN_API_TOKEN", "");
+        if admin_api_token.is_empty() {
+            info!("ADMIN_API_TOKEN is not set, admin routes are disabled");
+            return routes;
+        }
+
+        routes.nest("/admin", AdminRoutes::routes(app_state).layer(ValidateRequestHeaderLayer::bearer(&admin_api_token)))
     }
 }